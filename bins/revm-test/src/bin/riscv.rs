@@ -0,0 +1,157 @@
+use revm::{
+    db::BenchmarkDB,
+    interpreter::analysis::to_analysed,
+    primitives::{
+        address, bytes, Address, Bytecode, Bytes, ExecutionResult, TransactTo, RISCV_MAGIC, U256,
+    },
+    Evm,
+};
+use std::time::{Duration, Instant};
+
+/// Number of timed runs of every contract, after one warm-up run.
+const RUNS: u32 = 20;
+
+/// Work done by an EVM contract and a RISC-V contract alike.
+///
+/// Both contracts read the number of iterations from the first calldata word. The sources
+/// of the RISC-V contracts are next to their ELF files in `elf_test`.
+struct Workload {
+    name: &'static str,
+    iterations: u64,
+    evm: Bytes,
+    riscv: &'static [u8],
+}
+
+fn workloads() -> [Workload; 3] {
+    [
+        // buf = keccak256(buf) in a loop, then return(buf, 32):
+        //
+        //     PUSH1 0 CALLDATALOAD
+        //     loop: JUMPDEST DUP1 ISZERO PUSH1 done JUMPI
+        //     PUSH1 0x20 PUSH1 0 KECCAK256 PUSH1 0 MSTORE
+        //     PUSH1 1 SWAP1 SUB PUSH1 loop JUMP
+        //     done: JUMPDEST PUSH1 0x20 PUSH1 0 RETURN
+        Workload {
+            name: "keccak",
+            iterations: 10_000,
+            evm: bytes!("6000355b80156018576020600020600052600190036003565b60206000f3"),
+            riscv: include_bytes!("../../../../elf_test/bench_keccak"),
+        },
+        // sstore(i, i) for every i down to one, then return the sum of sload(i) for every i:
+        //
+        //     PUSH1 0 CALLDATALOAD
+        //     store: JUMPDEST DUP1 ISZERO PUSH1 load JUMPI
+        //     DUP1 DUP1 SSTORE PUSH1 1 SWAP1 SUB PUSH1 store JUMP
+        //     load: JUMPDEST POP PUSH1 0 CALLDATALOAD PUSH1 0
+        //     loop: JUMPDEST DUP2 ISZERO PUSH1 done JUMPI
+        //     DUP2 SLOAD ADD SWAP1 PUSH1 1 SWAP1 SUB SWAP1 PUSH1 loop JUMP
+        //     done: JUMPDEST PUSH1 0 MSTORE PUSH1 0x20 PUSH1 0 RETURN
+        Workload {
+            name: "storage",
+            iterations: 1_000,
+            evm: bytes!(
+                "6000355b8015601357808055600190036003565b5060003560005b8115602c57815401906001900390601a565b60005260206000f3"
+            ),
+            riscv: include_bytes!("../../../../elf_test/bench_storage"),
+        },
+        // call(gas, 0, 0, 0, 0, 0, 0) in a loop, then return the number of successful calls.
+        // The contract calls itself with empty calldata, so the nested calls return right
+        // away:
+        //
+        //     PUSH1 0 PUSH1 0 CALLDATALOAD
+        //     loop: JUMPDEST DUP1 ISZERO PUSH1 done JUMPI
+        //     PUSH1 0 DUP1 DUP1 DUP1 DUP1 DUP1 GAS CALL DUP3 ADD SWAP2 POP
+        //     PUSH1 1 SWAP1 SUB PUSH1 loop JUMP
+        //     done: JUMPDEST POP PUSH1 0 MSTORE PUSH1 0x20 PUSH1 0 RETURN
+        Workload {
+            name: "calls",
+            iterations: 1_000,
+            evm: bytes!(
+                "60006000355b8015601f57600080808080805af182019150600190036005565b5060005260206000f3"
+            ),
+            riscv: include_bytes!("../../../../elf_test/bench_calls"),
+        },
+    ]
+}
+
+/// Gas used by a call to a contract, its output and the mean wall time of the call.
+struct Measurement {
+    gas_used: u64,
+    output: Bytes,
+    elapsed: Duration,
+}
+
+impl Measurement {
+    /// Millions of gas executed per second.
+    fn mgas_per_second(&self) -> f64 {
+        self.gas_used as f64 / self.elapsed.as_secs_f64() / 1e6
+    }
+}
+
+fn measure(bytecode: Bytecode, iterations: u64) -> Measurement {
+    // BenchmarkDB is dummy state that implements Database trait.
+    // the bytecode is deployed at zero address.
+    let mut evm = Evm::builder()
+        .with_db(BenchmarkDB::new_bytecode(bytecode))
+        .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+        .modify_tx_env(|tx| {
+            tx.caller = address!("1000000000000000000000000000000000000000");
+            tx.transact_to = TransactTo::Call(Address::ZERO);
+            tx.data = U256::from(iterations).to_be_bytes_vec().into();
+        })
+        .build();
+
+    // Warm up and check that the contract runs to the end.
+    let (gas_used, output) = match evm.transact().unwrap().result {
+        ExecutionResult::Success {
+            gas_used, output, ..
+        } => (gas_used, output.into_data()),
+        result => panic!("Execution failed: {result:?}"),
+    };
+
+    let timer = Instant::now();
+    for _ in 0..RUNS {
+        let _ = evm.transact().unwrap();
+    }
+    Measurement {
+        gas_used,
+        output,
+        elapsed: timer.elapsed() / RUNS,
+    }
+}
+
+fn main() {
+    println!("Running RISC-V and EVM benchmarks, mean of {RUNS} runs");
+    for workload in workloads() {
+        let evm = measure(
+            to_analysed(Bytecode::new_raw(workload.evm)),
+            workload.iterations,
+        );
+        let mut code = vec![RISCV_MAGIC];
+        code.extend_from_slice(workload.riscv);
+        let riscv = measure(
+            Bytecode::new_riscv(code.into()).unwrap(),
+            workload.iterations,
+        );
+        assert_eq!(
+            evm.output, riscv.output,
+            "{}: EVM and RISC-V outputs differ",
+            workload.name
+        );
+
+        println!("{} ({} iterations):", workload.name, workload.iterations);
+        for (vm, measurement) in [("EVM", &evm), ("RISC-V", &riscv)] {
+            println!(
+                "  {vm:<6}  gas used: {:>10}  elapsed: {:>12?}  {:>8.2} Mgas/s",
+                measurement.gas_used,
+                measurement.elapsed,
+                measurement.mgas_per_second(),
+            );
+        }
+        println!(
+            "  RISC-V/EVM  gas: {:.2}  elapsed: {:.2}",
+            riscv.gas_used as f64 / evm.gas_used as f64,
+            riscv.elapsed.as_secs_f64() / evm.elapsed.as_secs_f64(),
+        );
+    }
+}
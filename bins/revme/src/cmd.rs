@@ -1,6 +1,7 @@
 pub mod bytecode;
 pub mod evmrunner;
 pub mod format_kzg_setup;
+pub mod riscv;
 pub mod statetest;
 
 use structopt::{clap::AppSettings, StructOpt};
@@ -21,6 +22,10 @@ pub enum MainCmd {
     Evm(evmrunner::Cmd),
     #[structopt(alias = "bc", about = "Prints the opcodes of an hex Bytecodes.")]
     Bytecode(bytecode::Cmd),
+    #[structopt(
+        about = "RISC-V runner command allows running and tracing a RISC-V contract from an ELF file."
+    )]
+    Riscv(riscv::Cmd),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -31,6 +36,8 @@ pub enum Error {
     KzgErrors(#[from] format_kzg_setup::KzgErrors),
     #[error(transparent)]
     EvmRunnerErrors(#[from] evmrunner::Errors),
+    #[error(transparent)]
+    RiscVErrors(#[from] riscv::Errors),
 }
 
 impl MainCmd {
@@ -43,6 +50,7 @@ impl MainCmd {
                 cmd.run();
                 Ok(())
             }
+            Self::Riscv(cmd) => cmd.run().map_err(Into::into),
         }
     }
 }
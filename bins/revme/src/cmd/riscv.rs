@@ -0,0 +1,323 @@
+use super::statetest::{models::AccountInfo, utils::decode_code};
+use revm::{
+    db::{BenchmarkDB, CacheDB, EmptyDB},
+    inspector_handle_register,
+    interpreter::{Interpreter, RVEmu, Syscall},
+    primitives::{
+        Address, Bytecode, Bytes, HashMap, ResultAndState, RiscVDecodeError, TransactTo,
+        RISCV_MAGIC,
+    },
+    Database, Evm, EvmContext, Inspector,
+};
+use std::{
+    fmt::Debug,
+    fs,
+    io::{Error as IoError, Write},
+    path::PathBuf,
+};
+use structopt::StructOpt;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Errors {
+    #[error("The specified path does not exist")]
+    PathNotExists,
+    #[error("Invalid RISC-V code: {0}")]
+    InvalidCode(RiscVDecodeError),
+    #[error("Invalid input")]
+    InvalidInput,
+    #[error("Invalid pre-state: {0}")]
+    InvalidPrestate(#[from] serde_json::Error),
+    #[error("EVM Error: {0}")]
+    EVMError(String),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Runs a RISC-V contract, given as an ELF file, through the full EVM.
+///
+/// The contract is deployed at the zero address and called from address one. Without a
+/// pre-state the contract runs on a dummy state, otherwise on an in-memory database with
+/// the accounts of the pre-state. The contract replaces the code of the zero address, its
+/// balance, nonce and storage are kept.
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    /// Path to the ELF file of the contract, with or without the RISC-V magic byte.
+    path: PathBuf,
+    /// Input bytes.
+    #[structopt(long, default_value = "")]
+    input: String,
+    /// Path to a JSON file with the accounts of the pre-state, in the format of the `pre`
+    /// field of state tests. Code that decodes as a RISC-V ELF is RISC-V code.
+    #[structopt(long)]
+    prestate: Option<PathBuf>,
+    /// Print every executed RISC-V instruction.
+    #[structopt(long)]
+    trace: bool,
+    /// Print every syscall with its arguments and results.
+    #[structopt(long)]
+    syscalls: bool,
+    /// Print the state.
+    #[structopt(long)]
+    state: bool,
+    /// Gas limit of the transaction.
+    #[structopt(long, default_value = "30000000")]
+    gas_limit: u64,
+}
+
+impl Cmd {
+    /// Run riscv command.
+    pub fn run(&self) -> Result<(), Errors> {
+        let out = self.transact(Box::new(std::io::stdout()))?;
+        println!("Result: {:#?}", out.result);
+
+        if self.state {
+            println!("State: {:#?}", out.state);
+        }
+
+        Ok(())
+    }
+
+    /// Runs the transaction calling the contract, writing the trace to `trace`.
+    fn transact(&self, trace: Box<dyn Write>) -> Result<ResultAndState, Errors> {
+        if !self.path.exists() {
+            return Err(Errors::PathNotExists);
+        }
+        let mut code = fs::read(&self.path)?;
+        if code.first() != Some(&RISCV_MAGIC) {
+            code.insert(0, RISCV_MAGIC);
+        }
+        let bytecode = Bytecode::new_riscv(code.into()).map_err(Errors::InvalidCode)?;
+        let input = hex::decode(self.input.trim())
+            .map_err(|_| Errors::InvalidInput)?
+            .into();
+
+        let Some(path) = &self.prestate else {
+            // BenchmarkDB is dummy state that implements Database trait.
+            // the bytecode is deployed at zero address.
+            return self.execute(BenchmarkDB::new_bytecode(bytecode), input, trace);
+        };
+        let prestate: HashMap<Address, AccountInfo> =
+            serde_json::from_str(&fs::read_to_string(path)?)?;
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, info) in prestate {
+            let code = decode_code(info.code);
+            let acc_info = revm::primitives::AccountInfo::new(
+                info.balance,
+                info.nonce,
+                code.hash_slow(),
+                code,
+            );
+            db.insert_account_info(address, acc_info);
+            for (slot, value) in info.storage {
+                // EmptyDB can't fail.
+                db.insert_account_storage(address, slot, value).unwrap();
+            }
+        }
+        let mut info = db
+            .accounts
+            .get(&Address::ZERO)
+            .map(|account| account.info.clone())
+            .unwrap_or_default();
+        info.code_hash = bytecode.hash_slow();
+        info.code = Some(bytecode);
+        db.insert_account_info(Address::ZERO, info);
+        self.execute(db, input, trace)
+    }
+
+    /// Calls the contract at the zero address with `input`.
+    fn execute<DB: Database>(
+        &self,
+        db: DB,
+        input: Bytes,
+        trace: Box<dyn Write>,
+    ) -> Result<ResultAndState, Errors>
+    where
+        DB::Error: Debug,
+    {
+        let evm = Evm::builder()
+            .with_db(db)
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .modify_tx_env(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.data = input;
+                tx.gas_limit = self.gas_limit;
+            });
+
+        if self.trace || self.syscalls {
+            let tracer = RiscVTracer::new(trace, self.trace, self.syscalls);
+            evm.with_external_context(tracer)
+                .append_handler_register(inspector_handle_register)
+                .build()
+                .transact()
+        } else {
+            evm.build().transact()
+        }
+        .map_err(|e| Errors::EVMError(format!("{e:?}")))
+    }
+}
+
+/// Inspector that prints the instructions and syscalls executed by RISC-V contracts.
+///
+/// Syscalls are printed with their arguments in `a0..a7` before they run, and with their
+/// results in `a0..a3` after.
+pub struct RiscVTracer {
+    output: Box<dyn Write>,
+    instructions: bool,
+    syscalls: bool,
+}
+
+impl RiscVTracer {
+    pub fn new(output: Box<dyn Write>, instructions: bool, syscalls: bool) -> Self {
+        Self {
+            output,
+            instructions,
+            syscalls,
+        }
+    }
+}
+
+/// Index of register `a0`.
+const A0: u64 = 10;
+
+/// Formats the first `count` argument registers, starting at `a0`.
+fn registers(riscv: &RVEmu, count: u64) -> String {
+    (A0..A0 + count)
+        .map(|i| format!("{:#x}", riscv.register(i)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl<DB: Database> Inspector<DB> for RiscVTracer {
+    fn riscv_step(
+        &mut self,
+        interp: &mut Interpreter,
+        _riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        pc: u64,
+        inst: u64,
+    ) {
+        if self.instructions {
+            let _ = writeln!(
+                self.output,
+                "depth={} pc={pc:#x} inst={inst:#010x} gas={}",
+                context.journaled_state.depth(),
+                interp.gas.remaining(),
+            );
+        }
+    }
+
+    fn riscv_syscall(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        syscall: Syscall,
+    ) {
+        if self.syscalls {
+            let args = registers(riscv, 8);
+            let _ = writeln!(
+                self.output,
+                "depth={} syscall={syscall:?} args=[{args}] gas={}",
+                context.journaled_state.depth(),
+                interp.gas.remaining(),
+            );
+        }
+    }
+
+    fn riscv_syscall_end(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        syscall: Syscall,
+    ) {
+        if self.syscalls {
+            let results = registers(riscv, 4);
+            let _ = writeln!(
+                self.output,
+                "depth={} syscall={syscall:?} results=[{results}] gas={} result={:?}",
+                context.journaled_state.depth(),
+                interp.gas.remaining(),
+                interp.instruction_result,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::{ExecutionResult, Output};
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Writer that keeps what is written to it.
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn calldata_example() {
+        let input: Vec<u8> = (0..42).collect();
+        let cmd = Cmd::from_iter([
+            "riscv",
+            "../../elf_test/calldata_example",
+            "--input",
+            &hex::encode(&input),
+            "--trace",
+            "--syscalls",
+        ]);
+        let buffer = Buffer::default();
+        let out = cmd.transact(Box::new(buffer.clone())).unwrap();
+
+        // the contract returns the calldata from offset 2.
+        let ExecutionResult::Success {
+            output: Output::Call(output),
+            ..
+        } = out.result
+        else {
+            panic!("call failed: {:?}", out.result);
+        };
+        assert_eq!(output[..], input[2..]);
+
+        let trace = String::from_utf8(buffer.0.take()).unwrap();
+        assert!(trace.starts_with("depth=1 pc=0x80000000 inst="));
+        let syscalls: Vec<_> = trace
+            .lines()
+            .filter_map(|line| line.strip_prefix("depth=1 syscall="))
+            .filter(|line| line.contains(" args="))
+            .map(|line| line.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(
+            syscalls,
+            [
+                "CallDataSize",
+                "SStore",
+                "CallDataLoad",
+                "SStore",
+                "CallDataCopy",
+                "Return"
+            ]
+        );
+        // the calldata size is returned in `a0`.
+        assert!(trace.contains("syscall=CallDataSize results=[0x2a, 0x0, 0x0, 0x0]"));
+    }
+
+    #[test]
+    fn invalid_code() {
+        let cmd = Cmd::from_iter(["riscv", "../../elf_test/calldata_example.s"]);
+        assert!(matches!(
+            cmd.transact(Box::new(std::io::sink())),
+            Err(Errors::InvalidCode(_))
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use super::{
     merkle_trie::{log_rlp_hash, state_merkle_trie_root},
     models::{SpecName, Test, TestSuite},
-    utils::recover_address,
+    utils::{decode_code, recover_address},
 };
 use indicatif::{ProgressBar, ProgressDrawTarget};
 use revm::{
@@ -9,8 +9,8 @@ use revm::{
     inspector_handle_register,
     inspectors::TracerEip3155,
     primitives::{
-        calc_excess_blob_gas, keccak256, Bytecode, Bytes, EVMResultGeneric, Env, ExecutionResult,
-        SpecId, TransactTo, B256, U256,
+        calc_excess_blob_gas, Bytes, EVMResultGeneric, Env, ExecutionResult, SpecId, TransactTo,
+        B256, U256,
     },
     Evm, State,
 };
@@ -256,11 +256,15 @@ pub fn execute_test_suite(
     for (name, unit) in suite.0 {
         // Create database and insert cache
         let mut cache_state = revm::CacheState::new(false);
+        // RISC-V execution is enabled for tests with RISC-V code in the pre-state.
+        let mut enable_riscv = false;
         for (address, info) in unit.pre {
+            let code = decode_code(info.code);
+            enable_riscv |= code.is_riscv();
             let acc_info = revm::primitives::AccountInfo {
                 balance: info.balance,
-                code_hash: keccak256(&info.code),
-                code: Some(Bytecode::new_raw(info.code)),
+                code_hash: code.hash_slow(),
+                code: Some(code),
                 nonce: info.nonce,
             };
             cache_state.insert_account_with_storage(address, acc_info, info.storage);
@@ -269,6 +273,7 @@ pub fn execute_test_suite(
         let mut env = Box::<Env>::default();
         // for mainnet
         env.cfg.chain_id = 1;
+        env.cfg.enable_riscv = enable_riscv;
         // env.cfg.spec_id is set down the road
 
         // block env
@@ -1,5 +1,5 @@
 use k256::ecdsa::SigningKey;
-use revm::primitives::Address;
+use revm::primitives::{Address, Bytecode, Bytes};
 
 /// Recover the address from a private key (SigningKey).
 pub fn recover_address(private_key: &[u8]) -> Option<Address> {
@@ -8,6 +8,13 @@ pub fn recover_address(private_key: &[u8]) -> Option<Address> {
     Some(Address::from_raw_public_key(&public_key.as_bytes()[1..]))
 }
 
+/// Decodes the code of a pre-state account. Code starting with the RISC-V magic byte that
+/// decodes as a RISC-V ELF is RISC-V code, like it is when it is called with RISC-V
+/// execution enabled, other code is legacy bytecode.
+pub fn decode_code(code: Bytes) -> Bytecode {
+    Bytecode::new_raw(code).resolve_riscv(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
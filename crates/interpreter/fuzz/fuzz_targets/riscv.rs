@@ -0,0 +1,10 @@
+//! Runs generated RISC-V programs through the interpreter, checking that guest input
+//! never panics it and that gas is only ever consumed.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use revm_interpreter_fuzz::RiscVFuzzInput;
+
+fuzz_target!(|input: RiscVFuzzInput| {
+    input.run();
+});
@@ -0,0 +1,329 @@
+//! Generated RISC-V programs for fuzzing the interpreter.
+//!
+//! Also run from a fixed stream of inputs by the `inputs` test of this crate.
+
+use arbitrary::Arbitrary;
+use revm_interpreter::{
+    opcode::make_instruction_table,
+    primitives::{Address, Bytecode, Bytes, CancunSpec, Env, Log, SpecId, B256, RISCV_MAGIC, U256},
+    CallOutcome, Contract, CreateOutcome, DummyHost, Gas, Host, InstructionResult, Interpreter,
+    InterpreterAction, InterpreterResult, LoadAccountResult, RVEmu, RiscVHooks, SStoreResult,
+    SelfDestructResult, Syscall, EMPTY_SHARED_MEMORY,
+};
+
+/// Address guest memory is mapped from.
+const DRAM_BASE: u64 = 0x8000_0000;
+
+/// Number of times a [`RiscVFuzzInput`] program is resumed after a call or create.
+pub const MAX_RESUMES: usize = 16;
+
+/// Size of the ELF64 file header.
+const EHDR_SIZE: usize = 64;
+/// Size of an ELF64 program header entry.
+const PHDR_SIZE: usize = 56;
+/// Number of instructions of the prologue that loads the registers.
+const PROLOGUE_LEN: u32 = 33;
+
+/// RISC-V program and environment generated by a fuzzer.
+///
+/// The program is a single executable segment that loads [`registers`] into `x1..x31`,
+/// runs [`body`] and returns. It is run by [`RiscVFuzzInput::run`], which checks the
+/// invariants of the interpreter.
+///
+/// [`registers`]: RiscVFuzzInput::registers
+/// [`body`]: RiscVFuzzInput::body
+#[derive(Arbitrary, Clone, Debug)]
+pub struct RiscVFuzzInput {
+    /// Page of DRAM the segment is loaded at.
+    pub page: u8,
+    /// Number of zeroed bytes the segment occupies past the program.
+    pub bss: u16,
+    /// Initial values of `x1..x31`.
+    pub registers: [FuzzRegister; 31],
+    /// Instructions run after the registers are loaded.
+    pub body: Vec<FuzzInstruction>,
+    /// Calldata of the call.
+    pub input: Vec<u8>,
+    /// Output of the calls and creates made by the program.
+    pub return_data: Vec<u8>,
+    /// Gas limit of the call.
+    pub gas_limit: u16,
+    /// DRAM limit, in pages.
+    pub dram_pages: u16,
+    /// Spec the program runs in, defaults to the latest if it is not a valid spec.
+    pub spec: u8,
+    /// Whether the call is static.
+    pub is_static: bool,
+}
+
+/// Initial value of a register of a [`RiscVFuzzInput`] program.
+#[derive(Arbitrary, Clone, Copy, Debug)]
+pub enum FuzzRegister {
+    /// Any value.
+    Value(u64),
+    /// Address at the given offset from the start of the program.
+    Memory(u16),
+}
+
+/// Instruction of the body of a [`RiscVFuzzInput`] program.
+#[derive(Arbitrary, Clone, Copy, Debug)]
+pub enum FuzzInstruction {
+    /// Any 32-bit encoding.
+    Raw(u32),
+    /// Any 16-bit encoding.
+    Compressed(u16),
+    /// `li t0, number` and `ecall`.
+    Syscall(Syscall),
+}
+
+impl RiscVFuzzInput {
+    /// Address the program is loaded at.
+    #[inline]
+    pub fn base(&self) -> u64 {
+        DRAM_BASE + self.page as u64 * 4096
+    }
+
+    /// Returns the contract code, [`RISCV_MAGIC`] followed by the ELF of the program.
+    pub fn code(&self) -> Bytes {
+        let text = self.text();
+        let base = self.base();
+        let offset = (EHDR_SIZE + PHDR_SIZE) as u64;
+
+        let mut code = Vec::with_capacity(1 + offset as usize + text.len());
+        code.push(RISCV_MAGIC);
+        // ELF header of a little-endian 64-bit RISC-V executable.
+        code.extend_from_slice(&[0x7F, b'E', b'L', b'F', 2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        code.extend_from_slice(&2u16.to_le_bytes());
+        code.extend_from_slice(&0xF3u16.to_le_bytes());
+        code.extend_from_slice(&1u32.to_le_bytes());
+        code.extend_from_slice(&base.to_le_bytes());
+        code.extend_from_slice(&(EHDR_SIZE as u64).to_le_bytes());
+        code.extend_from_slice(&0u64.to_le_bytes());
+        code.extend_from_slice(&0u32.to_le_bytes());
+        for value in [EHDR_SIZE, PHDR_SIZE, 1, 64, 0, 0] {
+            code.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+        // program header of the loadable, readable, writable and executable segment.
+        code.extend_from_slice(&1u32.to_le_bytes());
+        code.extend_from_slice(&7u32.to_le_bytes());
+        for value in [
+            offset,
+            base,
+            base,
+            text.len() as u64,
+            text.len() as u64 + self.bss as u64,
+            1,
+        ] {
+            code.extend_from_slice(&value.to_le_bytes());
+        }
+        code.extend_from_slice(&text);
+        code.into()
+    }
+
+    /// Returns the instructions and data of the program.
+    fn text(&self) -> Vec<u8> {
+        let mut text = Vec::new();
+
+        // auipc x31, 0
+        push(&mut text, 0xf97);
+        // ld x1..x31 from the data after the prologue, relative to x31.
+        let data = PROLOGUE_LEN * 4;
+        for reg in 1..=31 {
+            let offset = data + 8 * (reg - 1);
+            push(
+                &mut text,
+                offset << 20 | 31 << 15 | 3 << 12 | reg << 7 | 0x03,
+            );
+        }
+        // jal x0, over the data
+        push(&mut text, jal(4 + 31 * 8));
+
+        let base = self.base();
+        for register in self.registers {
+            let value = match register {
+                FuzzRegister::Value(value) => value,
+                FuzzRegister::Memory(offset) => base + offset as u64,
+            };
+            text.extend_from_slice(&value.to_le_bytes());
+        }
+
+        for inst in &self.body {
+            match *inst {
+                FuzzInstruction::Raw(inst) => push(&mut text, inst),
+                FuzzInstruction::Compressed(inst) => text.extend_from_slice(&inst.to_le_bytes()),
+                FuzzInstruction::Syscall(syscall) => {
+                    push(&mut text, li_t0(syscall as u32));
+                    push(&mut text, ECALL);
+                }
+            }
+        }
+        // return(a0, a1)
+        push(&mut text, li_t0(Syscall::Return as u32));
+        push(&mut text, ECALL);
+        text
+    }
+
+    /// Runs the program with a [`FuzzHost`] until it returns or has been resumed
+    /// [`MAX_RESUMES`] times, and returns its result if it returned.
+    ///
+    /// Calls and creates succeed with [`return_data`] as output and return all of their gas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the remaining gas grows while the program runs, or gets over the gas limit.
+    ///
+    /// [`return_data`]: RiscVFuzzInput::return_data
+    pub fn run(&self) -> Option<InterpreterResult> {
+        let gas_limit = self.gas_limit as u64;
+        let contract = Contract::new(
+            self.input.clone().into(),
+            Bytecode::new_riscv(self.code()).expect("generated ELF is valid"),
+            None,
+            Address::default(),
+            Address::default(),
+            U256::ZERO,
+        );
+        let mut interp = Interpreter::new(contract, gas_limit, self.is_static);
+        interp.set_spec_id(SpecId::try_from_u8(self.spec).unwrap_or(SpecId::LATEST));
+        let mut host = FuzzHost::default();
+        host.0.env.cfg.riscv_dram_size = self.dram_pages as u64 * 4096;
+        let table = make_instruction_table::<FuzzHost, CancunSpec>();
+
+        let mut shared_memory = EMPTY_SHARED_MEMORY;
+        for _ in 0..=MAX_RESUMES {
+            let mut check = GasCheck {
+                remaining: interp.gas.remaining(),
+            };
+            let action = interp.run_with_riscv_hooks(shared_memory, &table, &mut host, &mut check);
+            check.check(&interp);
+            assert!(interp.gas.remaining() <= gas_limit, "gas over the limit");
+
+            let outcome = |gas_limit| InterpreterResult {
+                result: InstructionResult::Return,
+                output: self.return_data.clone().into(),
+                gas: Gas::new(gas_limit),
+            };
+            shared_memory = interp.take_memory();
+            match action {
+                InterpreterAction::Call { inputs } => interp.insert_call_outcome(
+                    &mut shared_memory,
+                    CallOutcome::new(
+                        outcome(inputs.gas_limit),
+                        inputs.return_memory_offset.clone(),
+                    ),
+                ),
+                InterpreterAction::Create { inputs } => interp.insert_create_outcome(
+                    CreateOutcome::new(outcome(inputs.gas_limit), Some(Address::ZERO)),
+                ),
+                InterpreterAction::Return { result } => {
+                    assert_ne!(result.result, InstructionResult::Continue);
+                    return Some(result);
+                }
+                action => panic!("unexpected action {action:?}"),
+            }
+        }
+        None
+    }
+}
+
+/// [`DummyHost`] that also supports selfdestructs, so programs can reach the selfdestruct
+/// syscall.
+#[derive(Debug, Default)]
+pub struct FuzzHost(pub DummyHost);
+
+impl Host for FuzzHost {
+    fn env(&self) -> &Env {
+        self.0.env()
+    }
+
+    fn env_mut(&mut self) -> &mut Env {
+        self.0.env_mut()
+    }
+
+    fn load_account(&mut self, address: Address) -> Option<LoadAccountResult> {
+        self.0.load_account(address)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Option<B256> {
+        self.0.block_hash(number)
+    }
+
+    fn balance(&mut self, address: Address) -> Option<(U256, bool)> {
+        self.0.balance(address)
+    }
+
+    fn code(&mut self, address: Address) -> Option<(Bytecode, bool)> {
+        self.0.code(address)
+    }
+
+    fn code_hash(&mut self, address: Address) -> Option<(B256, bool)> {
+        self.0.code_hash(address)
+    }
+
+    fn sload(&mut self, address: Address, index: U256) -> Option<(U256, bool)> {
+        self.0.sload(address, index)
+    }
+
+    fn sstore(&mut self, address: Address, index: U256, value: U256) -> Option<SStoreResult> {
+        self.0.sstore(address, index, value)
+    }
+
+    fn tload(&mut self, address: Address, index: U256) -> U256 {
+        self.0.tload(address, index)
+    }
+
+    fn tstore(&mut self, address: Address, index: U256, value: U256) {
+        self.0.tstore(address, index, value)
+    }
+
+    fn log(&mut self, log: Log) {
+        self.0.log(log)
+    }
+
+    fn selfdestruct(&mut self, _address: Address, _target: Address) -> Option<SelfDestructResult> {
+        Some(SelfDestructResult::default())
+    }
+}
+
+/// Checks that the remaining gas never grows between instructions and syscalls.
+struct GasCheck {
+    /// Gas remaining after the last instruction or syscall.
+    remaining: u64,
+}
+
+impl GasCheck {
+    fn check(&mut self, interp: &Interpreter) {
+        let remaining = interp.gas.remaining();
+        assert!(remaining <= self.remaining, "remaining gas grew");
+        self.remaining = remaining;
+    }
+}
+
+impl RiscVHooks<FuzzHost> for GasCheck {
+    fn step(&mut self, interp: &mut Interpreter, _: &RVEmu, _: &mut FuzzHost, _: u64, _: u64) {
+        self.check(interp);
+    }
+
+    fn syscall_end(&mut self, interp: &mut Interpreter, _: &RVEmu, _: &mut FuzzHost, _: Syscall) {
+        self.check(interp);
+    }
+}
+
+/// Appends the 32-bit instruction `inst` to `text`.
+#[inline]
+fn push(text: &mut Vec<u8>, inst: u32) {
+    text.extend_from_slice(&inst.to_le_bytes());
+}
+
+/// `ecall`
+const ECALL: u32 = 0x73;
+
+/// `li t0, value`, for values below 2048.
+const fn li_t0(value: u32) -> u32 {
+    value << 20 | 5 << 7 | 0x13
+}
+
+/// `jal x0, offset`, for even offsets below 2048.
+const fn jal(offset: u32) -> u32 {
+    (offset & 0x7fe) << 20 | 0x6f
+}
@@ -0,0 +1,23 @@
+//! Runs the generated programs for a fixed stream of input, so every test run checks the
+//! same programs.
+
+use arbitrary::{Arbitrary, Unstructured};
+use revm_interpreter_fuzz::RiscVFuzzInput;
+
+#[test]
+fn fuzz_inputs() {
+    // xorshift64 stream.
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let bytes: Vec<u8> = (0..1 << 18)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect();
+    for chunk in bytes.chunks(1024) {
+        let input = RiscVFuzzInput::arbitrary_take_rest(Unstructured::new(chunk)).unwrap();
+        input.run();
+    }
+}
@@ -337,6 +337,39 @@ pub const fn warm_cold_cost(is_cold: bool) -> u64 {
     }
 }
 
+/// RISC-V instruction cost calculation.
+///
+/// `inst` is the raw instruction returned by the emulator. Compressed instructions are
+/// priced by their quadrant and funct3, uncompressed ones by their major opcode.
+#[inline]
+pub const fn riscv_instruction_cost(inst: u64) -> u64 {
+    if inst & 0b11 != 0b11 {
+        // Quadrants 0 and 2 hold the compressed loads and stores, with the exception of
+        // `c.addi4spn`, `c.slli` (funct3 = 0) and `c.jr`/`c.mv`/`c.add` (funct3 = 4).
+        let quadrant = inst & 0b11;
+        let funct3 = (inst >> 13) & 0b111;
+        return if quadrant != 1 && funct3 != 0 && funct3 != 4 {
+            RISCV_MEMORY
+        } else {
+            RISCV_BASE
+        };
+    }
+
+    match inst & 0x7f {
+        // LOAD, LOAD-FP, STORE, STORE-FP and AMO
+        0x03 | 0x07 | 0x23 | 0x27 | 0x2f => RISCV_MEMORY,
+        // OP and OP-32 with the M extension funct7
+        0x33 | 0x3b if inst >> 25 == 1 => {
+            if (inst >> 12) & 0b111 < 4 {
+                RISCV_MUL
+            } else {
+                RISCV_DIV
+            }
+        }
+        _ => RISCV_BASE,
+    }
+}
+
 /// Memory expansion cost calculation for a given memory length.
 #[inline]
 pub const fn memory_gas_for_len(len: usize) -> u64 {
@@ -51,3 +51,11 @@ pub const WARM_SSTORE_RESET: u64 = SSTORE_RESET - COLD_SLOAD_COST;
 pub const INITCODE_WORD_COST: u64 = 2;
 
 pub const CALL_STIPEND: u64 = 2300;
+
+// RISC-V instruction costs
+pub const RISCV_BASE: u64 = 1;
+pub const RISCV_MEMORY: u64 = 2;
+pub const RISCV_MUL: u64 = 3;
+pub const RISCV_DIV: u64 = 5;
+/// Cost of growing RISC-V guest memory by a 4 KiB page, 3 gas per 32-byte word.
+pub const RISCV_PAGE: u64 = 384;
@@ -1,4 +1,4 @@
-use crate::primitives::{Address, Bytecode, Env, Log, B256, U256};
+use crate::primitives::{Address, Bytecode, Bytes, Env, Log, PrecompileResult, B256, U256};
 
 mod dummy;
 pub use dummy::DummyHost;
@@ -47,6 +47,23 @@ pub trait Host {
 
     /// Mark `address` to be deleted, with funds transferred to `target`.
     fn selfdestruct(&mut self, address: Address, target: Address) -> Option<SelfDestructResult>;
+
+    /// Run the precompile at `address` with `input` and `gas_limit`, without a call frame.
+    ///
+    /// Returns `None` if there is no precompile at `address`, which is the default for hosts
+    /// without precompiles.
+    #[inline]
+    fn precompile(
+        &mut self,
+        address: Address,
+        input: &Bytes,
+        gas_limit: u64,
+    ) -> Option<PrecompileResult> {
+        let _ = address;
+        let _ = input;
+        let _ = gas_limit;
+        None
+    }
 }
 
 /// Represents the result of an `sstore` operation.
@@ -53,6 +53,18 @@ pub enum InstructionResult {
     EOFOpcodeDisabledInLegacy,
     /// EOF function stack overflow
     EOFFunctionStackOverflow,
+    /// Created contract starts with the RISC-V marker but is not a valid RISC-V ELF.
+    CreateContractInvalidRiscV,
+    /// RISC-V contract executed an illegal instruction.
+    RiscVInvalidInstruction,
+    /// RISC-V contract accessed memory or jumped to an address that is not aligned.
+    RiscVMisalignedAccess,
+    /// RISC-V contract accessed memory outside of its DRAM.
+    RiscVOutOfBounds,
+    /// RISC-V contract made a syscall with an unknown number.
+    RiscVUnknownSyscall,
+    /// RISC-V contract ran without a spec, see `Interpreter::set_spec_id`.
+    RiscVSpecNotSet,
 }
 
 impl From<SuccessReason> for InstructionResult {
@@ -88,6 +100,12 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::CreateContractSizeLimit => Self::CreateContractSizeLimit,
             HaltReason::CreateContractStartingWithEF => Self::CreateContractStartingWithEF,
             HaltReason::CreateInitCodeSizeLimit => Self::CreateInitCodeSizeLimit,
+            HaltReason::CreateContractInvalidRiscV => Self::CreateContractInvalidRiscV,
+            HaltReason::RiscVInvalidInstruction => Self::RiscVInvalidInstruction,
+            HaltReason::RiscVMisalignedAccess => Self::RiscVMisalignedAccess,
+            HaltReason::RiscVOutOfBounds => Self::RiscVOutOfBounds,
+            HaltReason::RiscVUnknownSyscall => Self::RiscVUnknownSyscall,
+            HaltReason::RiscVSpecNotSet => Self::RiscVSpecNotSet,
             HaltReason::OverflowPayment => Self::OverflowPayment,
             HaltReason::StateChangeDuringStaticCall => Self::StateChangeDuringStaticCall,
             HaltReason::CallNotAllowedInsideStatic => Self::CallNotAllowedInsideStatic,
@@ -145,6 +163,12 @@ macro_rules! return_error {
             | InstructionResult::ReturnContractInNotInitEOF
             | InstructionResult::EOFOpcodeDisabledInLegacy
             | InstructionResult::EOFFunctionStackOverflow
+            | InstructionResult::CreateContractInvalidRiscV
+            | InstructionResult::RiscVInvalidInstruction
+            | InstructionResult::RiscVMisalignedAccess
+            | InstructionResult::RiscVOutOfBounds
+            | InstructionResult::RiscVUnknownSyscall
+            | InstructionResult::RiscVSpecNotSet
     };
 }
 
@@ -266,6 +290,18 @@ impl From<InstructionResult> for SuccessOrHalt {
             InstructionResult::CreateInitCodeSizeLimit => {
                 Self::Halt(HaltReason::CreateInitCodeSizeLimit)
             }
+            InstructionResult::CreateContractInvalidRiscV => {
+                Self::Halt(HaltReason::CreateContractInvalidRiscV)
+            }
+            InstructionResult::RiscVInvalidInstruction => {
+                Self::Halt(HaltReason::RiscVInvalidInstruction)
+            }
+            InstructionResult::RiscVMisalignedAccess => {
+                Self::Halt(HaltReason::RiscVMisalignedAccess)
+            }
+            InstructionResult::RiscVOutOfBounds => Self::Halt(HaltReason::RiscVOutOfBounds),
+            InstructionResult::RiscVUnknownSyscall => Self::Halt(HaltReason::RiscVUnknownSyscall),
+            InstructionResult::RiscVSpecNotSet => Self::Halt(HaltReason::RiscVSpecNotSet),
             InstructionResult::FatalExternalError => Self::FatalExternalError,
             InstructionResult::EOFOpcodeDisabledInLegacy => Self::Halt(HaltReason::OpcodeNotFound),
             InstructionResult::EOFFunctionStackOverflow => Self::FatalExternalError,
@@ -340,6 +376,12 @@ mod tests {
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
             InstructionResult::FatalExternalError,
+            InstructionResult::CreateContractInvalidRiscV,
+            InstructionResult::RiscVInvalidInstruction,
+            InstructionResult::RiscVMisalignedAccess,
+            InstructionResult::RiscVOutOfBounds,
+            InstructionResult::RiscVUnknownSyscall,
+            InstructionResult::RiscVSpecNotSet,
         ];
 
         for result in error_results {
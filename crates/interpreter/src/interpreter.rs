@@ -1,11 +1,15 @@
 pub mod analysis;
 mod contract;
+#[cfg(feature = "riscv")]
+mod riscv;
 #[cfg(feature = "serde")]
 pub mod serde;
 mod shared_memory;
 mod stack;
 
 pub use contract::Contract;
+#[cfg(feature = "riscv")]
+pub use riscv::{RVEmu, RiscVHooks, Syscall};
 pub use shared_memory::{num_words, SharedMemory, EMPTY_SHARED_MEMORY};
 pub use stack::{Stack, STACK_LIMIT};
 
@@ -14,14 +18,13 @@ use crate::{
     gas, push, push_b256, return_ok, return_revert, CallOutcome, CreateOutcome, FunctionStack, Gas,
     Host, InstructionResult, InterpreterAction,
 };
-use crate::{CallInputs, CallScheme, CallValue};
 use core::cmp::min;
-use core::ops::Range;
-use revm_primitives::{Address, Bytecode, Bytes, Eof, U256};
+#[cfg(feature = "riscv")]
+use revm_primitives::spec_to_generic;
+use revm_primitives::{Bytecode, Bytes, Eof, SpecId, U256};
 use std::borrow::ToOwned;
-
-use eth_riscv_interpreter::setup_from_elf;
-use rvemu::{emulator::Emulator, exception::Exception};
+#[cfg(feature = "riscv")]
+use std::boxed::Box;
 
 /// EVM bytecode interpreter.
 #[derive(Debug)]
@@ -65,14 +68,9 @@ pub struct Interpreter {
     /// Set inside CALL or CREATE instructions and RETURN or REVERT instructions. Additionally those instructions will set
     /// InstructionResult to CallOrCreate/Return/Revert so we know the reason.
     pub next_action: InterpreterAction,
-
-    pub riscv_emulator: Option<RVEmu>,
-}
-
-#[derive(Debug)]
-struct RVEmu {
-    emu: Emulator,
-    returned_data_destiny: Option<Range<u64>>,
+    /// RISC-V emulator state, set if the contract is an ELF program.
+    #[cfg(feature = "riscv")]
+    pub riscv_emulator: Option<Box<RVEmu>>,
 }
 
 impl Default for Interpreter {
@@ -102,15 +100,8 @@ impl Interpreter {
         let is_eof = contract.bytecode.is_eof();
         let bytecode = contract.bytecode.bytecode().clone();
 
-        let riscv_emulator = if bytecode[0] == 0xFF {
-            let emu = setup_from_elf(&bytecode[1..], &contract.input);
-            Some(RVEmu {
-                emu,
-                returned_data_destiny: None,
-            })
-        } else {
-            None
-        };
+        #[cfg(feature = "riscv")]
+        let riscv_emulator = contract.bytecode.is_riscv().then(|| Box::new(RVEmu::new()));
 
         Self {
             instruction_pointer: bytecode.as_ptr(),
@@ -126,6 +117,7 @@ impl Interpreter {
             shared_memory: EMPTY_SHARED_MEMORY,
             stack: Stack::new(),
             next_action: InterpreterAction::None,
+            #[cfg(feature = "riscv")]
             riscv_emulator,
         }
     }
@@ -141,6 +133,20 @@ impl Interpreter {
         self.contract.bytecode.eof()
     }
 
+    /// Sets the spec used to price RISC-V syscalls. RISC-V contracts halt with
+    /// [`InstructionResult::RiscVSpecNotSet`] without it.
+    ///
+    /// EVM bytecode is priced by the instruction table, so this has no effect on it.
+    #[inline]
+    pub fn set_spec_id(&mut self, spec_id: SpecId) {
+        #[cfg(feature = "riscv")]
+        if let Some(riscv) = &mut self.riscv_emulator {
+            riscv.spec_id = Some(spec_id);
+        }
+        #[cfg(not(feature = "riscv"))]
+        let _ = spec_id;
+    }
+
     /// Test related helper
     #[cfg(test)]
     pub fn new_bytecode(bytecode: Bytecode) -> Self {
@@ -211,19 +217,19 @@ impl Interpreter {
         match instruction_result {
             return_ok!() => {
                 let address = create_outcome.address;
-                push_b256!(self, address.unwrap_or_default().into_word());
+                self.push_frame_result(address.unwrap_or_default().into_word().into());
                 self.gas.erase_cost(create_outcome.gas().remaining());
                 self.gas.record_refund(create_outcome.gas().refunded());
             }
             return_revert!() => {
-                push!(self, U256::ZERO);
+                self.push_frame_result(U256::ZERO);
                 self.gas.erase_cost(create_outcome.gas().remaining());
             }
             InstructionResult::FatalExternalError => {
                 panic!("Fatal external error in insert_create_outcome");
             }
             _ => {
-                push!(self, U256::ZERO);
+                self.push_frame_result(U256::ZERO);
             }
         }
     }
@@ -299,19 +305,19 @@ impl Interpreter {
                 let refunded = call_outcome.gas().refunded();
                 self.gas.erase_cost(remaining);
                 self.gas.record_refund(refunded);
-                shared_memory.set(out_offset, &self.return_data_buffer[..target_len]);
-                push!(self, U256::from(1));
+                self.set_call_output(shared_memory, out_offset, target_len);
+                self.push_frame_result(U256::from(1));
             }
             return_revert!() => {
                 self.gas.erase_cost(call_outcome.gas().remaining());
-                shared_memory.set(out_offset, &self.return_data_buffer[..target_len]);
-                push!(self, U256::ZERO);
+                self.set_call_output(shared_memory, out_offset, target_len);
+                self.push_frame_result(U256::ZERO);
             }
             InstructionResult::FatalExternalError => {
                 panic!("Fatal external error in insert_call_outcome");
             }
             _ => {
-                push!(self, U256::ZERO);
+                self.push_frame_result(U256::ZERO);
             }
         }
     }
@@ -380,152 +386,72 @@ impl Interpreter {
         instruction_table: &[FN; 256],
         host: &mut H,
     ) -> InterpreterAction
+    where
+        FN: Fn(&mut Interpreter, &mut H),
+    {
+        #[cfg(feature = "riscv")]
+        {
+            self.run_with_riscv_hooks(shared_memory, instruction_table, host, &mut ())
+        }
+        #[cfg(not(feature = "riscv"))]
+        {
+            self.next_action = InterpreterAction::None;
+            self.shared_memory = shared_memory;
+            self.run_evm(instruction_table, host)
+        }
+    }
+
+    /// Executes the interpreter like [`Interpreter::run`], calling `hooks` while a RISC-V
+    /// contract is executed.
+    #[cfg(feature = "riscv")]
+    pub fn run_with_riscv_hooks<FN, H: Host + ?Sized, R: RiscVHooks<H> + ?Sized>(
+        &mut self,
+        shared_memory: SharedMemory,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        hooks: &mut R,
+    ) -> InterpreterAction
     where
         FN: Fn(&mut Interpreter, &mut H),
     {
         self.next_action = InterpreterAction::None;
         self.shared_memory = shared_memory;
 
-        let mut resize_mem = None;
-
-        if let Some(RVEmu {
-            emu,
-            returned_data_destiny,
-        }) = &mut self.riscv_emulator
-        {
-            if let Some(destiny) = std::mem::take(returned_data_destiny) {
-                let data = emu.cpu.bus.get_dram_slice(destiny).unwrap();
-                data.copy_from_slice(self.shared_memory.slice(0, data.len()))
-            }
-
-            // Run emulator and capture ecalls
-            loop {
-                let run_result = emu.start();
-                match run_result {
-                    Err(Exception::EnvironmentCallFromMMode) => {
-                        let t0: u64 = emu.cpu.xregs.read(5);
-                        match t0 {
-                            0 => {
-                                // Syscall::Return
-                                let ret_offset: u64 = emu.cpu.xregs.read(10);
-                                let ret_size: u64 = emu.cpu.xregs.read(11);
-                                let data_bytes = if ret_size != 0 {
-                                    emu.cpu
-                                        .bus
-                                        .get_dram_slice(ret_offset..(ret_offset + ret_size))
-                                        .unwrap()
-                                } else {
-                                    &mut []
-                                };
-                                self.next_action = InterpreterAction::Return {
-                                    result: InterpreterResult {
-                                        result: InstructionResult::Return,
-                                        output: data_bytes.to_vec().into(),
-                                        gas: self.gas, // FIXME: gas is not correct
-                                    },
-                                };
-                                break;
-                            }
-                            1 => {
-                                // Syscall:SLoad
-                                let key: u64 = emu.cpu.xregs.read(10);
-                                match host.sload(self.contract.target_address, U256::from(key)) {
-                                    Some((value, is_cold)) => {
-                                        emu.cpu.xregs.write(10, value.as_limbs()[0]);
-                                    }
-                                    _ => {
-                                        self.instruction_result = InstructionResult::Revert;
-                                        break;
-                                    }
-                                }
-                            }
-                            2 => {
-                                // Syscall::SStore
-                                let key: u64 = emu.cpu.xregs.read(10);
-                                let value: u64 = emu.cpu.xregs.read(11);
-                                host.sstore(
-                                    self.contract.target_address,
-                                    U256::from(key),
-                                    U256::from(value),
-                                );
-                            }
-                            3 => {
-                                // Syscall::Call
-                                let a0: u64 = emu.cpu.xregs.read(10);
-                                let address = Address::from_slice(
-                                    emu.cpu.bus.get_dram_slice(a0..(a0 + 20)).unwrap(),
-                                );
-                                let value: u64 = emu.cpu.xregs.read(11);
-                                let args_offset: u64 = emu.cpu.xregs.read(12);
-                                let args_size: u64 = emu.cpu.xregs.read(13);
-                                let ret_offset = emu.cpu.xregs.read(14);
-                                let ret_size = emu.cpu.xregs.read(15);
-
-                                *returned_data_destiny = Some(ret_offset..(ret_offset + ret_size));
-
-                                if self.shared_memory.len() < ret_size as usize {
-                                    resize_mem = Some(ret_size as usize);
-                                }
-
-                                let tx = &host.env().tx;
-                                self.next_action = InterpreterAction::Call {
-                                    inputs: Box::new(CallInputs {
-                                        input: emu
-                                            .cpu
-                                            .bus
-                                            .get_dram_slice(args_offset..(args_offset + args_size))
-                                            .unwrap()
-                                            .to_vec()
-                                            .into(),
-                                        gas_limit: tx.gas_limit,
-                                        target_address: address,
-                                        bytecode_address: address,
-                                        caller: self.contract.target_address,
-                                        value: CallValue::Transfer(U256::from_le_bytes(
-                                            value.to_le_bytes(),
-                                        )),
-                                        scheme: CallScheme::Call,
-                                        is_static: false,
-                                        is_eof: false,
-                                        return_memory_offset: 0..ret_size as usize,
-                                    }),
-                                };
-                            }
-                            4 => {
-                                // Syscall::Revert
-                                self.next_action = InterpreterAction::Return {
-                                    result: InterpreterResult {
-                                        result: InstructionResult::Revert,
-                                        output: Bytes::from(0u32.to_le_bytes()), //TODO: return revert(0,0)
-                                        gas: self.gas, // FIXME: gas is not correct
-                                    },
-                                };
-                                break;
-                            }
-                            _ => {
-                                println!("Unhandled syscall: {:?}", t0);
-                                self.instruction_result = InstructionResult::Revert;
-                                break;
-                            }
-                        }
-                    }
-                    _ => {
-                        self.instruction_result = InstructionResult::Revert;
-                        break;
-                    }
-                }
-            }
-        } else {
-            // main loop
-            while self.instruction_result == InstructionResult::Continue {
-                self.step(instruction_table, host);
+        let Some(mut riscv) = self.riscv_emulator.take() else {
+            return self.run_evm(instruction_table, host);
+        };
+        // syscalls can't be priced without a spec.
+        match riscv.spec_id {
+            Some(spec_id) => {
+                spec_to_generic!(
+                    spec_id,
+                    self.run_riscv::<H, SPEC, R>(&mut riscv, host, hooks)
+                )
             }
+            None => self.instruction_result = InstructionResult::RiscVSpecNotSet,
         }
+        self.riscv_emulator = Some(riscv);
+        self.take_next_action()
+    }
 
-        if let Some(new_size) = resize_mem {
-            assert!(self.resize_memory(new_size));
+    /// Runs the main loop of the EVM bytecode interpreter.
+    fn run_evm<FN, H: Host + ?Sized>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+    ) -> InterpreterAction
+    where
+        FN: Fn(&mut Interpreter, &mut H),
+    {
+        // main loop
+        while self.instruction_result == InstructionResult::Continue {
+            self.step(instruction_table, host);
         }
+        self.take_next_action()
+    }
 
+    /// Returns the action of the stopped interpreter.
+    fn take_next_action(&mut self) -> InterpreterAction {
         // Return next action if it is some.
         if self.next_action.is_some() {
             return core::mem::take(&mut self.next_action);
@@ -537,7 +463,7 @@ impl Interpreter {
                 result: self.instruction_result,
                 // return empty bytecode
                 output: Bytes::new(),
-                gas: self.gas, // FIXME: gas is not correct
+                gas: self.gas,
             },
         }
     }
@@ -548,6 +474,20 @@ impl Interpreter {
     pub fn resize_memory(&mut self, new_size: usize) -> bool {
         resize_memory(&mut self.shared_memory, &mut self.gas, new_size)
     }
+
+    /// Copies `len` bytes of the return data buffer to memory at `offset`.
+    #[cfg(not(feature = "riscv"))]
+    #[inline]
+    fn set_call_output(&mut self, shared_memory: &mut SharedMemory, offset: usize, len: usize) {
+        shared_memory.set(offset, &self.return_data_buffer[..len]);
+    }
+
+    /// Pushes the result of a call or create to the stack.
+    #[cfg(not(feature = "riscv"))]
+    #[inline]
+    fn push_frame_result(&mut self, value: U256) {
+        push!(self, value);
+    }
 }
 
 impl InterpreterResult {
@@ -591,7 +531,11 @@ mod tests {
     use super::*;
     use crate::{opcode::InstructionTable, DummyHost};
     use revm_primitives::CancunSpec;
-    use std::{fs::File, io::Read};
+    #[cfg(feature = "riscv")]
+    use {
+        revm_primitives::RISCV_MAGIC,
+        std::{fs::File, io::Read},
+    };
 
     #[test]
     fn object_safety() {
@@ -609,8 +553,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "riscv")]
     fn riscv_interpreter_return() {
-        let mut runtime_bytes = vec![0xFF];
+        let mut runtime_bytes = vec![RISCV_MAGIC];
         File::open("../../elf_test/return_example")
             .unwrap()
             .read_to_end(&mut runtime_bytes)
@@ -618,7 +563,7 @@ mod tests {
 
         let contract = Contract::new(
             Bytes::new(),
-            Bytecode::new_raw(Bytes::from(runtime_bytes)),
+            Bytecode::new_riscv(Bytes::from(runtime_bytes)).unwrap(),
             None,
             crate::primitives::Address::default(),
             crate::primitives::Address::default(),
@@ -626,6 +571,7 @@ mod tests {
         );
 
         let mut interp = Interpreter::new(contract, u64::MAX, false);
+        interp.set_spec_id(SpecId::CANCUN);
         let mut host = crate::DummyHost::default();
         let table: InstructionTable<DummyHost> =
             crate::opcode::make_instruction_table::<DummyHost, CancunSpec>();
@@ -645,8 +591,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "riscv")]
     fn riscv_interpreter_sstore_and_sload() {
-        let mut runtime_bytes = vec![0xFF];
+        let mut runtime_bytes = vec![RISCV_MAGIC];
         File::open("../../elf_test/sstore_and_sload_example")
             .unwrap()
             .read_to_end(&mut runtime_bytes)
@@ -654,7 +601,7 @@ mod tests {
 
         let contract = Contract::new(
             Bytes::new(),
-            Bytecode::new_raw(Bytes::from(runtime_bytes)),
+            Bytecode::new_riscv(Bytes::from(runtime_bytes)).unwrap(),
             None,
             crate::primitives::Address::default(),
             crate::primitives::Address::default(),
@@ -662,11 +609,21 @@ mod tests {
         );
 
         let mut interp = Interpreter::new(contract, u64::MAX, false);
+        interp.set_spec_id(SpecId::CANCUN);
         let mut host = crate::DummyHost::default();
         let table: InstructionTable<DummyHost> =
             crate::opcode::make_instruction_table::<DummyHost, CancunSpec>();
 
-        let result = interp.run(EMPTY_SHARED_MEMORY, &table, &mut host);
-        println!("{result:#?}");
+        match interp.run(EMPTY_SHARED_MEMORY, &table, &mut host) {
+            InterpreterAction::Return { result } => {
+                assert_eq!(result.result, InstructionResult::Return);
+            }
+            _ => panic!("Expected return action"),
+        }
+
+        // Both key and value are wider than 64 bits.
+        let key = U256::from_limbs([42, 0, 0, 1]);
+        let value = U256::from_limbs([0xdeadbeef, 0x11, 0x22, 0x33]);
+        assert_eq!(host.storage.get(&key), Some(&value));
     }
 }
@@ -0,0 +1,1759 @@
+mod memory;
+#[cfg(feature = "serde")]
+mod serde;
+mod syscall;
+
+pub use syscall::Syscall;
+
+use super::Interpreter;
+use crate::{
+    as_usize_saturated, check, gas,
+    gas::warm_cold_cost,
+    gas_or_fail,
+    instructions::contract::calc_call_gas,
+    primitives::{
+        keccak256, Address, Bytes, Log, LogData, Spec,
+        SpecId::{self, *},
+        B256, KECCAK_EMPTY, U256,
+    },
+    push, refund, require_non_staticcall, CallInputs, CallScheme, CallValue, CreateInputs,
+    CreateScheme, Host, InstructionResult, InterpreterAction, InterpreterResult, LoadAccountResult,
+    SStoreResult, SharedMemory, MAX_INITCODE_SIZE,
+};
+use core::{cell::RefCell, cmp::min, ops::Range};
+use memory::GuestMemory;
+use std::{boxed::Box, vec::Vec};
+
+use rvemu::{emulator::Emulator, exception::Exception};
+
+/// RISC-V emulator state of a frame that executes an ELF contract.
+#[derive(Debug)]
+pub struct RVEmu {
+    /// The emulator running the contract.
+    ///
+    /// rvemu only hands out DRAM mutably, the cell lets snapshots of a paused frame read
+    /// it through a shared reference. Execution goes through [`RefCell::get_mut`].
+    pub(crate) emu: RefCell<Emulator>,
+    /// Guest memory, mapped when the frame first runs.
+    pub(crate) memory: Option<GuestMemory>,
+    /// Guest memory range the output of a pending call is copied to when its outcome is
+    /// inserted.
+    pub(crate) returned_data_destiny: Option<Range<u64>>,
+    /// Spec used to price the syscalls, set with [`Interpreter::set_spec_id`].
+    pub(crate) spec_id: Option<SpecId>,
+}
+
+impl Default for RVEmu {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RVEmu {
+    /// Creates a new emulator.
+    ///
+    /// The ELF segments of the contract are mapped into guest memory when the frame first
+    /// runs, once the DRAM limit of [`CfgEnv`] is known. Calldata is not mapped into guest
+    /// memory, contracts read it with the calldata syscalls instead.
+    ///
+    /// [`CfgEnv`]: crate::primitives::CfgEnv
+    pub fn new() -> Self {
+        Self {
+            emu: RefCell::new(Emulator::new()),
+            memory: None,
+            returned_data_destiny: None,
+            spec_id: None,
+        }
+    }
+}
+
+impl RVEmu {
+    /// Address of the next instruction to execute.
+    #[inline]
+    pub fn pc(&self) -> u64 {
+        self.emu.borrow().cpu.pc
+    }
+
+    /// Value of the integer register `x{index}`.
+    #[inline]
+    pub fn register(&self, index: u64) -> u64 {
+        self.emu.borrow().cpu.xregs.read(index)
+    }
+}
+
+/// Callbacks into the execution of a RISC-V contract, see
+/// [`Interpreter::run_with_riscv_hooks`].
+///
+/// All methods do nothing by default. The emulator state, such as the program counter and
+/// the registers, is available on `riscv`.
+pub trait RiscVHooks<H: ?Sized> {
+    /// Called after an instruction other than `ecall` has been executed, with the address
+    /// `pc` and the encoding `inst` of the instruction.
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter, riscv: &RVEmu, host: &mut H, pc: u64, inst: u64) {
+        let _ = interp;
+        let _ = riscv;
+        let _ = host;
+        let _ = pc;
+        let _ = inst;
+    }
+
+    /// Called before a syscall is executed, with its arguments in the registers.
+    #[inline]
+    fn syscall(&mut self, interp: &mut Interpreter, riscv: &RVEmu, host: &mut H, syscall: Syscall) {
+        let _ = interp;
+        let _ = riscv;
+        let _ = host;
+        let _ = syscall;
+    }
+
+    /// Called after a syscall has been executed, with its results in the registers.
+    ///
+    /// Call and create syscalls end when the call or create is requested, their results
+    /// are only written when the frame is resumed.
+    #[inline]
+    fn syscall_end(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        host: &mut H,
+        syscall: Syscall,
+    ) {
+        let _ = interp;
+        let _ = riscv;
+        let _ = host;
+        let _ = syscall;
+    }
+}
+
+impl<H: ?Sized> RiscVHooks<H> for () {}
+
+impl Interpreter {
+    /// Runs the RISC-V emulator until the contract returns, halts or requests a call.
+    ///
+    /// Every executed instruction is charged with [`gas::riscv_instruction_cost`] and
+    /// syscalls are additionally charged like their EVM opcode counterparts. Guest memory
+    /// grows as it is accessed, up to the DRAM limit of the config, and every new page
+    /// is charged [`gas::RISCV_PAGE`].
+    pub(crate) fn run_riscv<H: Host + ?Sized, SPEC: Spec, R: RiscVHooks<H> + ?Sized>(
+        &mut self,
+        riscv: &mut RVEmu,
+        host: &mut H,
+        hooks: &mut R,
+    ) {
+        if riscv.memory.is_none() {
+            let limit = host.env().cfg.riscv_dram_size;
+            let Some(bytecode) = self.contract.bytecode.riscv() else {
+                self.instruction_result = InstructionResult::RiscVOutOfBounds;
+                return;
+            };
+            let Some((memory, pages)) = GuestMemory::new(bytecode, limit) else {
+                self.instruction_result = InstructionResult::RiscVOutOfBounds;
+                return;
+            };
+            // the memory is only allocated once it is paid for.
+            gas!(self, pages.saturating_mul(gas::RISCV_PAGE));
+            if memory.load(riscv.emu.get_mut(), bytecode).is_none() {
+                self.instruction_result = InstructionResult::RiscVOutOfBounds;
+                return;
+            }
+            riscv.memory = Some(memory);
+        }
+
+        while self.instruction_result == InstructionResult::Continue {
+            let pc = riscv.emu.get_mut().cpu.pc;
+            // the instruction and the memory it accesses are checked before it is executed,
+            // so the emulator never touches memory that has not been charged for.
+            let Some(inst) = riscv
+                .memory
+                .as_ref()
+                .and_then(|memory| memory.fetch(riscv.emu.get_mut(), pc))
+            else {
+                self.instruction_result = InstructionResult::RiscVOutOfBounds;
+                continue;
+            };
+            if let Some((addr, len)) = memory::memory_access(riscv.emu.get_mut(), inst) {
+                if !self.riscv_access(riscv, addr, len) {
+                    continue;
+                }
+            }
+
+            match riscv.emu.get_mut().cpu.execute() {
+                Ok(inst) => {
+                    gas!(self, gas::riscv_instruction_cost(inst));
+                    hooks.step(self, riscv, host, pc, inst);
+                }
+                Err(Exception::EnvironmentCallFromMMode) => {
+                    gas!(self, gas::RISCV_BASE);
+                    let t0: u64 = riscv.emu.get_mut().cpu.xregs.read(5);
+                    let Some(syscall) = Syscall::from_u64(t0) else {
+                        self.instruction_result = InstructionResult::RiscVUnknownSyscall;
+                        continue;
+                    };
+                    hooks.syscall(self, riscv, host, syscall);
+                    self.riscv_syscall::<H, SPEC>(riscv, host, syscall);
+                    hooks.syscall_end(self, riscv, host, syscall);
+                }
+                Err(exception) => {
+                    self.instruction_result = exception_result(exception);
+                }
+            }
+        }
+    }
+
+    /// Makes `len` bytes of guest memory at `addr` accessible, charging
+    /// [`gas::RISCV_PAGE`] for every page the memory grows by.
+    ///
+    /// Returns false and halts with [`InstructionResult::RiscVOutOfBounds`] if the range
+    /// is outside of the DRAM limit, or with [`InstructionResult::OutOfGas`].
+    fn riscv_access(&mut self, riscv: &mut RVEmu, addr: u64, len: u64) -> bool {
+        if len == 0 {
+            return true;
+        }
+        let Some(memory) = &mut riscv.memory else {
+            self.instruction_result = InstructionResult::RiscVOutOfBounds;
+            return false;
+        };
+        let Some(pages) = memory.pages_to_access(addr, len) else {
+            self.instruction_result = InstructionResult::RiscVOutOfBounds;
+            return false;
+        };
+        if pages != 0 {
+            gas!(self, pages.saturating_mul(gas::RISCV_PAGE), false);
+            memory.grow(riscv.emu.get_mut(), pages);
+        }
+        true
+    }
+
+    /// Returns `len` bytes of guest memory at `addr`, see [`Interpreter::riscv_access`].
+    fn riscv_memory<'a>(
+        &mut self,
+        riscv: &'a mut RVEmu,
+        addr: u64,
+        len: u64,
+    ) -> Option<&'a mut [u8]> {
+        if !self.riscv_access(riscv, addr, len) {
+            return None;
+        }
+        if len == 0 {
+            return Some(&mut []);
+        }
+        match riscv.emu.get_mut().cpu.bus.get_dram_slice(addr..addr + len) {
+            Ok(data) => Some(data),
+            Err(_) => {
+                self.instruction_result = InstructionResult::RiscVOutOfBounds;
+                None
+            }
+        }
+    }
+
+    /// Executes `syscall` with the arguments in the argument registers.
+    ///
+    /// See [`Syscall`] for the numbers and register usage of each syscall.
+    fn riscv_syscall<H: Host + ?Sized, SPEC: Spec>(
+        &mut self,
+        riscv: &mut RVEmu,
+        host: &mut H,
+        syscall: Syscall,
+    ) {
+        let emu = riscv.emu.get_mut();
+        match syscall {
+            Syscall::Return | Syscall::Revert => {
+                let ret_offset: u64 = emu.cpu.xregs.read(10);
+                let ret_size: u64 = emu.cpu.xregs.read(11);
+                let Some(output) = self.riscv_memory(riscv, ret_offset, ret_size) else {
+                    return;
+                };
+                let output = Bytes::copy_from_slice(output);
+                let result = if syscall == Syscall::Return {
+                    InstructionResult::Return
+                } else {
+                    InstructionResult::Revert
+                };
+                self.instruction_result = result;
+                self.next_action = InterpreterAction::Return {
+                    result: InterpreterResult {
+                        result,
+                        output,
+                        gas: self.gas,
+                    },
+                };
+            }
+            Syscall::SLoad => {
+                let key = read_u256(emu, 10);
+                let Some((value, is_cold)) = host.sload(self.contract.target_address, key) else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+                gas!(self, gas::sload_cost(SPEC::SPEC_ID, is_cold));
+                write_u256(emu, 10, value);
+            }
+            Syscall::SStore => {
+                require_non_staticcall!(self);
+                let key = read_u256(emu, 10);
+                let value = read_u256(emu, 14);
+                let Some(SStoreResult {
+                    original_value: original,
+                    present_value: old,
+                    new_value: new,
+                    is_cold,
+                }) = host.sstore(self.contract.target_address, key, value)
+                else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+                gas_or_fail!(self, {
+                    let remaining_gas = self.gas.remaining();
+                    gas::sstore_cost(SPEC::SPEC_ID, original, old, new, remaining_gas, is_cold)
+                });
+                refund!(self, gas::sstore_refund(SPEC::SPEC_ID, original, old, new));
+            }
+            Syscall::Call => self.riscv_call::<H, SPEC>(riscv, host, CallScheme::Call),
+            Syscall::CallCode => self.riscv_call::<H, SPEC>(riscv, host, CallScheme::CallCode),
+            Syscall::DelegateCall => {
+                check!(self, HOMESTEAD);
+                self.riscv_call::<H, SPEC>(riscv, host, CallScheme::DelegateCall)
+            }
+            Syscall::StaticCall => {
+                check!(self, BYZANTIUM);
+                self.riscv_call::<H, SPEC>(riscv, host, CallScheme::StaticCall)
+            }
+            Syscall::Precompile => self.riscv_precompile(riscv, host),
+            Syscall::Create => self.riscv_create::<H, SPEC>(riscv, host, false),
+            Syscall::Create2 => {
+                check!(self, PETERSBURG);
+                self.riscv_create::<H, SPEC>(riscv, host, true)
+            }
+            Syscall::Keccak256 => {
+                let offset: u64 = emu.cpu.xregs.read(10);
+                let len: u64 = emu.cpu.xregs.read(11);
+                gas_or_fail!(self, gas::keccak256_cost(len));
+                let hash = if len == 0 {
+                    KECCAK_EMPTY
+                } else {
+                    let Some(data) = self.riscv_memory(riscv, offset, len) else {
+                        return;
+                    };
+                    keccak256(data)
+                };
+                write_u256(riscv.emu.get_mut(), 10, hash.into());
+            }
+            Syscall::Address => {
+                gas!(self, gas::BASE);
+                write_address(emu, 10, self.contract.target_address);
+            }
+            Syscall::Balance => {
+                let address = read_address(emu, 10);
+                let Some((balance, is_cold)) = host.balance(address) else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+                gas!(
+                    self,
+                    if SPEC::enabled(BERLIN) {
+                        warm_cold_cost(is_cold)
+                    } else if SPEC::enabled(ISTANBUL) {
+                        // EIP-1884: Repricing for trie-size-dependent opcodes
+                        700
+                    } else if SPEC::enabled(TANGERINE) {
+                        400
+                    } else {
+                        20
+                    }
+                );
+                write_u256(emu, 10, balance);
+            }
+            Syscall::Origin => {
+                gas!(self, gas::BASE);
+                write_address(emu, 10, host.env().tx.caller);
+            }
+            Syscall::Caller => {
+                gas!(self, gas::BASE);
+                write_address(emu, 10, self.contract.caller);
+            }
+            Syscall::CallValue => {
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, self.contract.call_value);
+            }
+            Syscall::CallDataLoad => {
+                gas!(self, gas::VERYLOW);
+                let offset = as_usize_saturated!(read_u256(emu, 10));
+                let input = &self.contract.input;
+                let mut word = B256::ZERO;
+                if offset < input.len() {
+                    let count = min(32, input.len() - offset);
+                    word[..count].copy_from_slice(&input[offset..offset + count]);
+                }
+                write_u256(emu, 10, word.into());
+            }
+            Syscall::CallDataSize => {
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, U256::from(self.contract.input.len()));
+            }
+            Syscall::CallDataCopy => {
+                let memory_offset: u64 = emu.cpu.xregs.read(10);
+                let data_offset: u64 = emu.cpu.xregs.read(11);
+                let len: u64 = emu.cpu.xregs.read(12);
+                gas_or_fail!(self, gas::verylowcopy_cost(len));
+                if len == 0 {
+                    return;
+                }
+                let Some(data) = self.riscv_memory(riscv, memory_offset, len) else {
+                    return;
+                };
+                let input = &self.contract.input;
+                let data_offset = min(data_offset as usize, input.len());
+                let copied = min(len as usize, input.len() - data_offset);
+                data[..copied].copy_from_slice(&input[data_offset..data_offset + copied]);
+                data[copied..].fill(0);
+            }
+            Syscall::GasPrice => {
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, host.env().effective_gas_price());
+            }
+            Syscall::ExtCodeSize => {
+                let address = read_address(emu, 10);
+                let Some((code, is_cold)) = host.code(address) else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+                if SPEC::enabled(BERLIN) {
+                    gas!(self, warm_cold_cost(is_cold));
+                } else if SPEC::enabled(TANGERINE) {
+                    gas!(self, 700);
+                } else {
+                    gas!(self, 20);
+                }
+                write_u256(emu, 10, U256::from(code.len()));
+            }
+            Syscall::ExtCodeCopy => {
+                let address = read_address(emu, 10);
+                let memory_offset: u64 = emu.cpu.xregs.read(14);
+                let code_offset: u64 = emu.cpu.xregs.read(15);
+                let len: u64 = emu.cpu.xregs.read(16);
+                let Some((code, is_cold)) = host.code(address) else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+                gas_or_fail!(self, gas::extcodecopy_cost(SPEC::SPEC_ID, len, is_cold));
+                if len == 0 {
+                    return;
+                }
+                let Some(data) = self.riscv_memory(riscv, memory_offset, len) else {
+                    return;
+                };
+                let code = code.original_byte_slice();
+                let code_offset = min(code_offset as usize, code.len());
+                let copied = min(len as usize, code.len() - code_offset);
+                data[..copied].copy_from_slice(&code[code_offset..code_offset + copied]);
+                data[copied..].fill(0);
+            }
+            Syscall::ReturnDataSize => {
+                check!(self, BYZANTIUM);
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, U256::from(self.return_data_buffer.len()));
+            }
+            Syscall::ReturnDataCopy => {
+                check!(self, BYZANTIUM);
+                let memory_offset: u64 = emu.cpu.xregs.read(10);
+                let offset: u64 = emu.cpu.xregs.read(11);
+                let len: u64 = emu.cpu.xregs.read(12);
+                gas_or_fail!(self, gas::verylowcopy_cost(len));
+                let data_offset = offset as usize;
+                let data_end = data_offset.saturating_add(len as usize);
+                if data_end > self.return_data_buffer.len() {
+                    self.instruction_result = InstructionResult::OutOfOffset;
+                    return;
+                }
+                let Some(data) = self.riscv_memory(riscv, memory_offset, len) else {
+                    return;
+                };
+                data.copy_from_slice(&self.return_data_buffer[data_offset..data_end]);
+            }
+            Syscall::ExtCodeHash => {
+                check!(self, CONSTANTINOPLE);
+                let address = read_address(emu, 10);
+                let Some((code_hash, is_cold)) = host.code_hash(address) else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+                if SPEC::enabled(BERLIN) {
+                    gas!(self, warm_cold_cost(is_cold));
+                } else if SPEC::enabled(ISTANBUL) {
+                    gas!(self, 700);
+                } else {
+                    gas!(self, 400);
+                }
+                write_u256(emu, 10, code_hash.into());
+            }
+            Syscall::BlockHash => {
+                gas!(self, gas::BLOCKHASH);
+                let number = read_u256(emu, 10);
+                let Some(hash) = host.block_hash(number) else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+                write_u256(emu, 10, hash.into());
+            }
+            Syscall::Coinbase => {
+                gas!(self, gas::BASE);
+                write_address(emu, 10, host.env().block.coinbase);
+            }
+            Syscall::Timestamp => {
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, host.env().block.timestamp);
+            }
+            Syscall::Number => {
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, host.env().block.number);
+            }
+            Syscall::PrevRandao => {
+                gas!(self, gas::BASE);
+                let value = if SPEC::enabled(MERGE) {
+                    host.env().block.prevrandao.unwrap().into()
+                } else {
+                    host.env().block.difficulty
+                };
+                write_u256(emu, 10, value);
+            }
+            Syscall::GasLimit => {
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, host.env().block.gas_limit);
+            }
+            Syscall::ChainId => {
+                check!(self, ISTANBUL);
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, U256::from(host.env().cfg.chain_id));
+            }
+            Syscall::SelfBalance => {
+                check!(self, ISTANBUL);
+                gas!(self, gas::LOW);
+                let Some((balance, _)) = host.balance(self.contract.target_address) else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+                write_u256(emu, 10, balance);
+            }
+            Syscall::BaseFee => {
+                check!(self, LONDON);
+                gas!(self, gas::BASE);
+                write_u256(emu, 10, host.env().block.basefee);
+            }
+            Syscall::BlobHash => {
+                check!(self, CANCUN);
+                gas!(self, gas::VERYLOW);
+                let index = as_usize_saturated!(read_u256(emu, 10));
+                let hash = match host.env().tx.blob_hashes.get(index) {
+                    Some(hash) => U256::from_be_bytes(hash.0),
+                    None => U256::ZERO,
+                };
+                write_u256(emu, 10, hash);
+            }
+            Syscall::BlobBaseFee => {
+                check!(self, CANCUN);
+                gas!(self, gas::BASE);
+                let blob_gasprice = host.env().block.get_blob_gasprice().unwrap_or_default();
+                write_u256(emu, 10, U256::from(blob_gasprice));
+            }
+            Syscall::TLoad => {
+                check!(self, CANCUN);
+                gas!(self, gas::WARM_STORAGE_READ_COST);
+                let key = read_u256(emu, 10);
+                write_u256(emu, 10, host.tload(self.contract.target_address, key));
+            }
+            Syscall::TStore => {
+                check!(self, CANCUN);
+                require_non_staticcall!(self);
+                gas!(self, gas::WARM_STORAGE_READ_COST);
+                let key = read_u256(emu, 10);
+                let value = read_u256(emu, 14);
+                host.tstore(self.contract.target_address, key, value);
+            }
+            Syscall::SelfDestruct => {
+                require_non_staticcall!(self);
+                let target = read_address(emu, 10);
+                let Some(res) = host.selfdestruct(self.contract.target_address, target) else {
+                    self.instruction_result = InstructionResult::FatalExternalError;
+                    return;
+                };
+
+                // EIP-3529: Reduction in refunds
+                if !SPEC::enabled(LONDON) && !res.previously_destroyed {
+                    refund!(self, gas::SELFDESTRUCT)
+                }
+                gas!(self, gas::selfdestruct_cost(SPEC::SPEC_ID, res));
+
+                self.instruction_result = InstructionResult::SelfDestruct;
+            }
+            Syscall::Log0 | Syscall::Log1 | Syscall::Log2 | Syscall::Log3 | Syscall::Log4 => {
+                require_non_staticcall!(self);
+                let topics_len = syscall as u64 - Syscall::Log0 as u64;
+                let data_offset: u64 = emu.cpu.xregs.read(10);
+                let len: u64 = emu.cpu.xregs.read(11);
+                let topic_offsets: [u64; 4] =
+                    core::array::from_fn(|i| emu.cpu.xregs.read(12 + i as u64));
+                gas_or_fail!(self, gas::log_cost(topics_len as u8, len));
+                let Some(data) = self.riscv_memory(riscv, data_offset, len) else {
+                    return;
+                };
+                let data = Bytes::copy_from_slice(data);
+
+                let mut topics = Vec::with_capacity(topics_len as usize);
+                for &topic in &topic_offsets[..topics_len as usize] {
+                    let Some(topic) = self.riscv_memory(riscv, topic, 32) else {
+                        return;
+                    };
+                    topics.push(B256::from_slice(topic));
+                }
+
+                let log = Log {
+                    address: self.contract.target_address,
+                    data: LogData::new(topics, data).expect("LogData should have <=4 topics"),
+                };
+
+                host.log(log);
+            }
+        }
+    }
+
+    /// Executes one of the call syscalls, see [`Syscall`] for their register layout.
+    ///
+    /// Calls are set up like the `CALL`, `CALLCODE`, `DELEGATECALL` and `STATICCALL`
+    /// instructions.
+    fn riscv_call<H: Host + ?Sized, SPEC: Spec>(
+        &mut self,
+        riscv: &mut RVEmu,
+        host: &mut H,
+        scheme: CallScheme,
+    ) {
+        let emu = riscv.emu.get_mut();
+        let local_gas_limit = emu.cpu.xregs.read(10);
+        let to_ptr = emu.cpu.xregs.read(11);
+        let (value_ptr, first) = match scheme {
+            CallScheme::Call | CallScheme::CallCode => (Some(emu.cpu.xregs.read(12)), 13),
+            CallScheme::DelegateCall | CallScheme::StaticCall => (None, 12),
+        };
+        let args_offset = emu.cpu.xregs.read(first);
+        let args_size = emu.cpu.xregs.read(first + 1);
+        let ret_offset = emu.cpu.xregs.read(first + 2);
+        let ret_size = emu.cpu.xregs.read(first + 3);
+
+        let value = match value_ptr {
+            Some(value_ptr) => {
+                let Some(value) = self.riscv_memory(riscv, value_ptr, 32) else {
+                    return;
+                };
+                U256::from_be_slice(value)
+            }
+            None => U256::ZERO,
+        };
+        let has_transfer = value != U256::ZERO;
+        if scheme == CallScheme::Call && self.is_static && has_transfer {
+            self.instruction_result = InstructionResult::CallNotAllowedInsideStatic;
+            return;
+        }
+
+        let Some(to) = self.riscv_memory(riscv, to_ptr, 20) else {
+            return;
+        };
+        let to = Address::from_slice(to);
+        let Some(input) = self.riscv_memory(riscv, args_offset, args_size) else {
+            return;
+        };
+        let input = Bytes::copy_from_slice(input);
+        if !self.riscv_access(riscv, ret_offset, ret_size) {
+            return;
+        }
+
+        let Some(LoadAccountResult { is_cold, is_empty }) = host.load_account(to) else {
+            self.instruction_result = InstructionResult::FatalExternalError;
+            return;
+        };
+        // only a `CALL` can create the callee account.
+        let is_empty = is_empty && scheme == CallScheme::Call;
+        let Some(mut gas_limit) =
+            calc_call_gas::<SPEC>(self, is_cold, has_transfer, is_empty, local_gas_limit)
+        else {
+            return;
+        };
+        gas!(self, gas_limit);
+
+        // add call stipend if there is value to be transferred.
+        if has_transfer {
+            gas_limit = gas_limit.saturating_add(gas::CALL_STIPEND);
+        }
+
+        riscv.returned_data_destiny = Some(ret_offset..(ret_offset + ret_size));
+
+        let (target_address, caller, value) = match scheme {
+            CallScheme::Call | CallScheme::StaticCall => {
+                (to, self.contract.target_address, CallValue::Transfer(value))
+            }
+            CallScheme::CallCode => (
+                self.contract.target_address,
+                self.contract.target_address,
+                CallValue::Transfer(value),
+            ),
+            CallScheme::DelegateCall => (
+                self.contract.target_address,
+                self.contract.caller,
+                CallValue::Apparent(self.contract.call_value),
+            ),
+        };
+        self.next_action = InterpreterAction::Call {
+            inputs: Box::new(CallInputs {
+                input,
+                gas_limit,
+                target_address,
+                bytecode_address: to,
+                caller,
+                value,
+                scheme,
+                is_static: self.is_static || scheme == CallScheme::StaticCall,
+                is_eof: false,
+                // relative to the output range in guest memory, see `set_call_output`.
+                return_memory_offset: 0..ret_size as usize,
+            }),
+        };
+        self.instruction_result = InstructionResult::CallOrCreate;
+    }
+
+    /// Executes [`Syscall::Precompile`].
+    ///
+    /// The precompile is run by the host right away, and its output is handled like the
+    /// output of a call that does not change the state.
+    fn riscv_precompile<H: Host + ?Sized>(&mut self, riscv: &mut RVEmu, host: &mut H) {
+        let emu = riscv.emu.get_mut();
+        let address_ptr: u64 = emu.cpu.xregs.read(10);
+        let args_offset: u64 = emu.cpu.xregs.read(11);
+        let args_size: u64 = emu.cpu.xregs.read(12);
+        let gas_limit = min(emu.cpu.xregs.read(13), self.gas.remaining());
+        let ret_offset: u64 = emu.cpu.xregs.read(14);
+        let ret_size: u64 = emu.cpu.xregs.read(15);
+
+        let Some(address) = self.riscv_memory(riscv, address_ptr, 20) else {
+            return;
+        };
+        let address = Address::from_slice(address);
+        let Some(input) = self.riscv_memory(riscv, args_offset, args_size) else {
+            return;
+        };
+        let input = Bytes::copy_from_slice(input);
+        if !self.riscv_access(riscv, ret_offset, ret_size) {
+            return;
+        }
+
+        let output = match host.precompile(address, &input, gas_limit) {
+            Some(Ok((gas_used, output))) if gas_used <= gas_limit => {
+                gas!(self, gas_used);
+                Some(output)
+            }
+            Some(_) => {
+                gas!(self, gas_limit);
+                None
+            }
+            None => None,
+        };
+        let success = output.is_some();
+        self.return_data_buffer = output.unwrap_or_default();
+
+        let len = min(ret_size as usize, self.return_data_buffer.len());
+        if len != 0 {
+            let data = &self.return_data_buffer[..len];
+            if let Ok(dest) = riscv
+                .emu
+                .get_mut()
+                .cpu
+                .bus
+                .get_dram_slice(ret_offset..ret_offset + len as u64)
+            {
+                dest.copy_from_slice(data);
+            }
+        }
+        write_u256(riscv.emu.get_mut(), 10, U256::from(success as u8));
+    }
+
+    /// Executes a create syscall, mirroring the `CREATE` and `CREATE2` instructions.
+    fn riscv_create<H: Host + ?Sized, SPEC: Spec>(
+        &mut self,
+        riscv: &mut RVEmu,
+        host: &mut H,
+        is_create2: bool,
+    ) {
+        require_non_staticcall!(self);
+        let emu = riscv.emu.get_mut();
+        let value_ptr: u64 = emu.cpu.xregs.read(10);
+        let code_offset: u64 = emu.cpu.xregs.read(11);
+        let len: u64 = emu.cpu.xregs.read(12);
+        let salt = read_u256(emu, 13);
+        let Some(value) = self.riscv_memory(riscv, value_ptr, 32) else {
+            return;
+        };
+        let value = U256::from_be_slice(value);
+
+        let mut init_code = Bytes::new();
+        if len != 0 {
+            // EIP-3860: Limit and meter initcode
+            if SPEC::enabled(SHANGHAI) {
+                // Limit is set as double of max contract bytecode size
+                let max_initcode_size = host
+                    .env()
+                    .cfg
+                    .limit_contract_code_size
+                    .map(|limit| limit.saturating_mul(2))
+                    .unwrap_or(MAX_INITCODE_SIZE);
+                if len > max_initcode_size as u64 {
+                    self.instruction_result = InstructionResult::CreateInitCodeSizeLimit;
+                    return;
+                }
+                gas!(self, gas::initcode_cost(len));
+            }
+            let Some(code) = self.riscv_memory(riscv, code_offset, len) else {
+                return;
+            };
+            init_code = Bytes::copy_from_slice(code);
+        }
+
+        let scheme = if is_create2 {
+            gas_or_fail!(self, gas::create2_cost(len));
+            CreateScheme::Create2 { salt }
+        } else {
+            gas!(self, gas::CREATE);
+            CreateScheme::Create
+        };
+
+        let mut gas_limit = self.gas.remaining();
+        // EIP-150: Gas cost changes for IO-heavy operations
+        if SPEC::enabled(TANGERINE) {
+            // take remaining gas and deduce l64 part of it.
+            gas_limit -= gas_limit / 64
+        }
+        gas!(self, gas_limit);
+
+        self.next_action = InterpreterAction::Create {
+            inputs: Box::new(CreateInputs {
+                caller: self.contract.target_address,
+                scheme,
+                value,
+                init_code,
+                gas_limit,
+            }),
+        };
+        self.instruction_result = InstructionResult::CallOrCreate;
+    }
+
+    /// Copies `len` bytes of the output of a call to the memory of the frame: to the output
+    /// range of the call syscall for RISC-V frames, which was made accessible when the call
+    /// was made, and to shared memory at `offset` otherwise.
+    pub(crate) fn set_call_output(
+        &mut self,
+        shared_memory: &mut SharedMemory,
+        offset: usize,
+        len: usize,
+    ) {
+        let data = &self.return_data_buffer[..len];
+        let Some(riscv) = &mut self.riscv_emulator else {
+            shared_memory.set(offset, data);
+            return;
+        };
+        let Some(destiny) = riscv.returned_data_destiny.take() else {
+            return;
+        };
+        if data.is_empty() {
+            return;
+        }
+        let start = destiny.start + offset as u64;
+        if let Ok(dest) = riscv
+            .emu
+            .get_mut()
+            .cpu
+            .bus
+            .get_dram_slice(start..start + data.len() as u64)
+        {
+            dest.copy_from_slice(data);
+        }
+    }
+
+    /// Hands the result of a call or create back to the frame, written to `a0..a3` for
+    /// RISC-V frames and pushed on the stack otherwise.
+    pub(crate) fn push_frame_result(&mut self, value: U256) {
+        match &mut self.riscv_emulator {
+            Some(riscv) => write_u256(riscv.emu.get_mut(), 10, value),
+            None => push!(self, value),
+        }
+    }
+}
+
+/// Maps an emulator exception, other than an ecall from machine mode, to the result the
+/// frame halts with.
+#[inline]
+fn exception_result(exception: Exception) -> InstructionResult {
+    match exception {
+        Exception::InstructionAddressMisaligned
+        | Exception::LoadAddressMisaligned
+        | Exception::StoreAMOAddressMisaligned => InstructionResult::RiscVMisalignedAccess,
+        Exception::InstructionAccessFault
+        | Exception::LoadAccessFault
+        | Exception::StoreAMOAccessFault
+        | Exception::InstructionPageFault(_)
+        | Exception::LoadPageFault(_)
+        | Exception::StoreAMOPageFault(_) => InstructionResult::RiscVOutOfBounds,
+        _ => InstructionResult::RiscVInvalidInstruction,
+    }
+}
+
+/// Reads a word passed to a RISC-V syscall as four little-endian 64-bit limbs
+/// in consecutive registers, starting with the least significant limb in `first`.
+#[inline]
+fn read_u256(emu: &Emulator, first: u64) -> U256 {
+    U256::from_limbs(core::array::from_fn(|i| {
+        emu.cpu.xregs.read(first + i as u64)
+    }))
+}
+
+/// Writes a word returned from a RISC-V syscall as four little-endian 64-bit limbs
+/// in consecutive registers, starting with the least significant limb in `first`.
+#[inline]
+fn write_u256(emu: &mut Emulator, first: u64, value: U256) {
+    for (i, limb) in value.as_limbs().iter().enumerate() {
+        emu.cpu.xregs.write(first + i as u64, *limb);
+    }
+}
+
+/// Reads an address passed to a RISC-V syscall as a word, see [`read_u256`].
+#[inline]
+fn read_address(emu: &Emulator, first: u64) -> Address {
+    Address::from_word(B256::from(read_u256(emu, first)))
+}
+
+/// Writes an address returned from a RISC-V syscall as a word, see [`write_u256`].
+#[inline]
+fn write_address(emu: &mut Emulator, first: u64, address: Address) {
+    write_u256(emu, first, address.into_word().into());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        opcode::{make_instruction_table, InstructionTable},
+        primitives::{
+            address, b256, Bytecode, CancunSpec, KECCAK_EMPTY, RISCV_DRAM_SIZE, RISCV_MAGIC,
+        },
+        CallOutcome, Contract, CreateOutcome, DummyHost, Gas, SharedMemory, EMPTY_SHARED_MEMORY,
+    };
+    use rvemu::bus::DRAM_BASE;
+    use std::{fs::File, io::Read};
+
+    fn riscv_interpreter(elf: &str, gas_limit: u64) -> Interpreter {
+        let mut runtime_bytes = vec![RISCV_MAGIC];
+        File::open(format!("../../elf_test/{elf}"))
+            .unwrap()
+            .read_to_end(&mut runtime_bytes)
+            .unwrap();
+
+        let contract = Contract::new(
+            Bytes::new(),
+            Bytecode::new_riscv(Bytes::from(runtime_bytes)).unwrap(),
+            None,
+            Address::default(),
+            Address::default(),
+            U256::ZERO,
+        );
+        let mut interp = Interpreter::new(contract, gas_limit, false);
+        interp.set_spec_id(SpecId::CANCUN);
+        interp
+    }
+
+    fn run(interp: &mut Interpreter, host: &mut DummyHost) -> InterpreterResult {
+        let table: InstructionTable<DummyHost> = make_instruction_table::<DummyHost, CancunSpec>();
+        match interp.run(EMPTY_SHARED_MEMORY, &table, host) {
+            InterpreterAction::Return { result } => result,
+            action => panic!("Expected return action, got {action:?}"),
+        }
+    }
+
+    fn run_call(interp: &mut Interpreter, host: &mut DummyHost) -> Box<CallInputs> {
+        let table: InstructionTable<DummyHost> = make_instruction_table::<DummyHost, CancunSpec>();
+        match interp.run(EMPTY_SHARED_MEMORY, &table, host) {
+            InterpreterAction::Call { inputs } => inputs,
+            action => panic!("Expected call action, got {action:?}"),
+        }
+    }
+
+    /// Runs `call_example` with the given call syscall up to the call.
+    fn call_example(syscall: Syscall, gas_limit: u64, is_static: bool) -> Interpreter {
+        let mut interp = riscv_interpreter("call_example", gas_limit);
+        interp.contract.input = Bytes::from(vec![syscall as u8]);
+        interp.contract.target_address = address!("1000000000000000000000000000000000000001");
+        interp.contract.caller = address!("2000000000000000000000000000000000000002");
+        interp.contract.call_value = U256::from(3);
+        interp.is_static = is_static;
+        interp
+    }
+
+    #[test]
+    fn spec_is_required() {
+        let mut interp = riscv_interpreter("return_example", u64::MAX);
+        interp.riscv_emulator.as_mut().unwrap().spec_id = None;
+        let result = run(&mut interp, &mut DummyHost::default());
+        assert_eq!(result.result, InstructionResult::RiscVSpecNotSet);
+        assert!(result.output.is_empty());
+    }
+
+    #[test]
+    fn legacy_code_is_not_sniffed() {
+        let interp = riscv_interpreter("return_example", u64::MAX);
+        assert!(interp.riscv_emulator.is_some());
+
+        // same bytes as raw legacy code, starting with SELFDESTRUCT.
+        let bytes = interp.contract.bytecode.original_bytes();
+        let contract = Contract::new(
+            Bytes::new(),
+            Bytecode::new_raw(bytes),
+            None,
+            Address::default(),
+            Address::default(),
+            U256::ZERO,
+        );
+        let interp = Interpreter::new(contract, u64::MAX, false);
+        assert!(interp.riscv_emulator.is_none());
+        assert!(!interp.contract.bytecode.is_riscv());
+    }
+
+    #[test]
+    fn env_syscalls() {
+        let mut interp = riscv_interpreter("env_example", u64::MAX);
+        interp.contract.target_address = address!("1000000000000000000000000000000000000001");
+        interp.contract.caller = address!("2000000000000000000000000000000000000002");
+        interp.contract.call_value = U256::from(3);
+        let mut host = DummyHost::default();
+        host.env.tx.caller = address!("4000000000000000000000000000000000000004");
+        host.env.tx.gas_price = U256::from(5);
+        host.env.tx.blob_hashes = vec![b256!(
+            "0100000000000000000000000000000000000000000000000000000000000006"
+        )];
+        host.env.block.coinbase = address!("7000000000000000000000000000000000000007");
+        host.env.block.timestamp = U256::from(8);
+        host.env.block.number = U256::from(9);
+        host.env.block.prevrandao = Some(B256::with_last_byte(10));
+        host.env.block.gas_limit = U256::from(11);
+        host.env.block.basefee = U256::from(12);
+        host.env.cfg.chain_id = 13;
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::Return);
+
+        let slot = |syscall: Syscall| host.storage[&U256::from(syscall as u64)];
+        let word = |address: Address| U256::from_be_bytes(address.into_word().0);
+        assert_eq!(slot(Syscall::Address), word(interp.contract.target_address));
+        assert_eq!(slot(Syscall::Balance), U256::ZERO);
+        assert_eq!(slot(Syscall::Origin), word(host.env.tx.caller));
+        assert_eq!(slot(Syscall::Caller), word(interp.contract.caller));
+        assert_eq!(slot(Syscall::CallValue), U256::from(3));
+        assert_eq!(slot(Syscall::GasPrice), U256::from(5));
+        assert_eq!(slot(Syscall::ExtCodeSize), U256::ZERO);
+        assert_eq!(
+            slot(Syscall::ExtCodeHash),
+            U256::from_be_bytes(KECCAK_EMPTY.0)
+        );
+        assert_eq!(slot(Syscall::BlockHash), U256::ZERO);
+        assert_eq!(slot(Syscall::Coinbase), word(host.env.block.coinbase));
+        assert_eq!(slot(Syscall::Timestamp), U256::from(8));
+        assert_eq!(slot(Syscall::Number), U256::from(9));
+        assert_eq!(slot(Syscall::PrevRandao), U256::from(10));
+        assert_eq!(slot(Syscall::GasLimit), U256::from(11));
+        assert_eq!(slot(Syscall::ChainId), U256::from(13));
+        assert_eq!(slot(Syscall::SelfBalance), U256::ZERO);
+        assert_eq!(slot(Syscall::BaseFee), U256::from(12));
+        assert_eq!(
+            slot(Syscall::BlobHash),
+            U256::from_be_bytes(host.env.tx.blob_hashes[0].0)
+        );
+        assert_eq!(slot(Syscall::BlobBaseFee), U256::from(1));
+    }
+
+    #[test]
+    fn syscalls_require_spec() {
+        let mut interp = riscv_interpreter("env_example", u64::MAX);
+        interp.set_spec_id(SpecId::BERLIN);
+        let mut host = DummyHost::default();
+
+        // BASEFEE is only available since London.
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::NotActivated);
+        let stored = |syscall: Syscall| host.storage.contains_key(&U256::from(syscall as u64));
+        assert!(stored(Syscall::SelfBalance));
+        assert!(!stored(Syscall::BaseFee));
+    }
+
+    #[test]
+    fn log_syscall() {
+        let mut interp = riscv_interpreter("log_example", u64::MAX);
+        interp.contract.target_address = address!("1000000000000000000000000000000000000001");
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(host.log.len(), 1);
+        let log = &host.log[0];
+        assert_eq!(log.address, interp.contract.target_address);
+        assert_eq!(
+            log.topics(),
+            [B256::with_last_byte(1), B256::with_last_byte(2)]
+        );
+        assert_eq!(log.data.data, Bytes::from_static(b"hello"));
+        assert!(result.gas.spent() > gas::log_cost(2, 5).unwrap());
+    }
+
+    #[test]
+    fn log_syscall_in_static_call() {
+        let mut interp = riscv_interpreter("log_example", u64::MAX);
+        interp.is_static = true;
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(
+            result.result,
+            InstructionResult::StateChangeDuringStaticCall
+        );
+        assert!(host.log.is_empty());
+    }
+
+    #[test]
+    fn keccak256_syscall() {
+        let mut interp = riscv_interpreter("keccak_example", u64::MAX);
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(
+            host.storage[&U256::ZERO],
+            U256::from_be_bytes(keccak256(b"hello").0)
+        );
+        assert_eq!(
+            host.storage[&U256::from(1)],
+            U256::from_be_bytes(KECCAK_EMPTY.0)
+        );
+        assert!(
+            result.gas.spent() > gas::keccak256_cost(5).unwrap() + gas::keccak256_cost(0).unwrap()
+        );
+    }
+
+    #[test]
+    fn precompile_syscall_without_precompile() {
+        let mut interp = riscv_interpreter("precompile_example", 1_000_000);
+        let mut input = Address::with_last_byte(2).to_vec();
+        input.extend_from_slice(b"abc");
+        interp.contract.input = input.into();
+        let mut host = DummyHost::default();
+
+        // the dummy host has no precompiles, the syscall fails without consuming gas.
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::Revert);
+        assert!(result.gas.spent() < 100_000);
+        assert!(interp.return_data_buffer.is_empty());
+    }
+
+    #[test]
+    fn selfdestruct_syscall_in_static_call() {
+        let mut interp = riscv_interpreter("selfdestruct_example", u64::MAX);
+        interp.is_static = true;
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(
+            result.result,
+            InstructionResult::StateChangeDuringStaticCall
+        );
+    }
+
+    #[test]
+    fn transient_storage_syscalls() {
+        let mut interp = riscv_interpreter("transient_example", u64::MAX);
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(host.transient_storage[&U256::from(1)], U256::from(7));
+        assert_eq!(host.storage[&U256::ZERO], U256::from(7));
+        assert!(result.gas.spent() > 2 * gas::WARM_STORAGE_READ_COST);
+
+        // the transient storage is not available before Cancun.
+        let mut interp = riscv_interpreter("transient_example", u64::MAX);
+        interp.set_spec_id(SpecId::SHANGHAI);
+        let mut host = DummyHost::default();
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::NotActivated);
+        assert!(host.transient_storage.is_empty());
+
+        let mut interp = riscv_interpreter("transient_example", u64::MAX);
+        interp.is_static = true;
+        let mut host = DummyHost::default();
+        let result = run(&mut interp, &mut host);
+        assert_eq!(
+            result.result,
+            InstructionResult::StateChangeDuringStaticCall
+        );
+        assert!(host.transient_storage.is_empty());
+    }
+
+    #[test]
+    fn instruction_costs() {
+        // addi a0, a0, 1
+        assert_eq!(gas::riscv_instruction_cost(0x00150513), gas::RISCV_BASE);
+        // ld a0, 0(sp)
+        assert_eq!(gas::riscv_instruction_cost(0x00013503), gas::RISCV_MEMORY);
+        // mul a0, a0, a1
+        assert_eq!(gas::riscv_instruction_cost(0x02b50533), gas::RISCV_MUL);
+        // divu a0, a0, a1
+        assert_eq!(gas::riscv_instruction_cost(0x02b55533), gas::RISCV_DIV);
+        // c.li a0, 0
+        assert_eq!(gas::riscv_instruction_cost(0x4501), gas::RISCV_BASE);
+        // c.sdsp ra, 8(sp)
+        assert_eq!(gas::riscv_instruction_cost(0xe406), gas::RISCV_MEMORY);
+    }
+
+    #[test]
+    fn infinite_loop_runs_out_of_gas() {
+        let mut interp = riscv_interpreter("infinite_loop_example", 10_000);
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::OutOfGas);
+        assert!(result.gas.remaining() < gas::RISCV_BASE);
+    }
+
+    #[test]
+    fn storage_syscalls_are_charged() {
+        let mut interp = riscv_interpreter("sstore_and_sload_example", 100_000);
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::Return);
+
+        // Cold SSTORE of a new slot followed by a warm SLOAD, plus one gas
+        // per executed instruction.
+        let storage_cost = gas::SSTORE_SET + gas::COLD_SLOAD_COST + gas::WARM_STORAGE_READ_COST;
+        let spent = result.gas.spent();
+        assert!(spent > storage_cost);
+        assert!(spent < storage_cost + 100);
+    }
+
+    #[test]
+    fn sstore_fails_without_stipend() {
+        let mut interp = riscv_interpreter("sstore_and_sload_example", gas::CALL_STIPEND);
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::OutOfGas);
+    }
+
+    #[test]
+    fn call_syscalls() {
+        let callee = address!("1111111111111111111111111111111111111122");
+        let this = address!("1000000000000000000000000000000000000001");
+        let caller = address!("2000000000000000000000000000000000000002");
+        // the value does not fit in a register.
+        let value = (U256::from(1) << 64) + U256::from(7);
+        let cases = [
+            (Syscall::Call, this, callee, CallValue::Transfer(value)),
+            (Syscall::CallCode, this, this, CallValue::Transfer(value)),
+            (
+                Syscall::DelegateCall,
+                caller,
+                this,
+                CallValue::Apparent(U256::from(3)),
+            ),
+            (
+                Syscall::StaticCall,
+                this,
+                callee,
+                CallValue::Transfer(U256::ZERO),
+            ),
+        ];
+        for (syscall, expected_caller, target, value) in cases {
+            let mut interp = call_example(syscall, 1_000_000, false);
+            let inputs = run_call(&mut interp, &mut DummyHost::default());
+            assert_eq!(interp.instruction_result, InstructionResult::CallOrCreate);
+            assert_eq!(inputs.input, Bytes::from_static(b"ping"), "{syscall:?}");
+            assert_eq!(inputs.caller, expected_caller, "{syscall:?}");
+            assert_eq!(inputs.target_address, target, "{syscall:?}");
+            assert_eq!(inputs.bytecode_address, callee, "{syscall:?}");
+            assert_eq!(inputs.value, value, "{syscall:?}");
+            assert_eq!(
+                inputs.is_static,
+                syscall == Syscall::StaticCall,
+                "{syscall:?}"
+            );
+            assert_eq!(inputs.return_memory_offset, 0..32, "{syscall:?}");
+            match syscall {
+                Syscall::Call | Syscall::CallCode => {
+                    assert_eq!(inputs.gas_limit, 50_000 + gas::CALL_STIPEND, "{syscall:?}")
+                }
+                _ => assert_eq!(inputs.gas_limit, 50_000, "{syscall:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn call_gas_is_capped() {
+        let mut interp = call_example(Syscall::StaticCall, 40_000, false);
+        let inputs = run_call(&mut interp, &mut DummyHost::default());
+
+        // the call keeps one 64th of the gas remaining after the call cost.
+        let remaining = interp.gas.remaining() + inputs.gas_limit;
+        assert!(inputs.gas_limit < 50_000);
+        assert_eq!(interp.gas.remaining(), remaining / 64);
+    }
+
+    #[test]
+    fn call_syscalls_in_static_call() {
+        for syscall in [
+            Syscall::CallCode,
+            Syscall::DelegateCall,
+            Syscall::StaticCall,
+        ] {
+            let mut interp = call_example(syscall, 1_000_000, true);
+            let inputs = run_call(&mut interp, &mut DummyHost::default());
+            assert!(inputs.is_static, "{syscall:?}");
+        }
+
+        // CALL with value.
+        let mut interp = call_example(Syscall::Call, 1_000_000, true);
+        let result = run(&mut interp, &mut DummyHost::default());
+        assert_eq!(result.result, InstructionResult::CallNotAllowedInsideStatic);
+    }
+
+    #[test]
+    fn sstore_syscall_in_static_call() {
+        let mut interp = riscv_interpreter("sstore_and_sload_example", u64::MAX);
+        interp.is_static = true;
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(
+            result.result,
+            InstructionResult::StateChangeDuringStaticCall
+        );
+        assert!(host.storage.is_empty());
+    }
+
+    #[test]
+    fn create_syscalls() {
+        let mut interp = riscv_interpreter("deploy_example", 1_000_000);
+        interp.contract.target_address = address!("1000000000000000000000000000000000000001");
+        let mut host = DummyHost::default();
+        let table: InstructionTable<DummyHost> = make_instruction_table::<DummyHost, CancunSpec>();
+        let mut init_code = vec![RISCV_MAGIC];
+        File::open("../../elf_test/create_example")
+            .unwrap()
+            .read_to_end(&mut init_code)
+            .unwrap();
+
+        let InterpreterAction::Create { inputs } =
+            interp.run(EMPTY_SHARED_MEMORY, &table, &mut host)
+        else {
+            panic!("Expected create action");
+        };
+        assert_eq!(inputs.caller, interp.contract.target_address);
+        assert_eq!(inputs.scheme, CreateScheme::Create);
+        assert_eq!(inputs.init_code, init_code);
+        assert_eq!(inputs.value, U256::ZERO);
+        let created = address!("3000000000000000000000000000000000000003");
+        interp.insert_create_outcome(CreateOutcome::new(
+            InterpreterResult {
+                result: InstructionResult::Return,
+                output: Bytes::new(),
+                gas: Gas::new(inputs.gas_limit),
+            },
+            Some(created),
+        ));
+
+        let InterpreterAction::Create { inputs } =
+            interp.run(EMPTY_SHARED_MEMORY, &table, &mut host)
+        else {
+            panic!("Expected create action");
+        };
+        assert_eq!(
+            inputs.scheme,
+            CreateScheme::Create2 {
+                salt: U256::from(1)
+            }
+        );
+        interp.insert_create_outcome(CreateOutcome::new(
+            InterpreterResult {
+                result: InstructionResult::Revert,
+                output: Bytes::new(),
+                gas: Gas::new(inputs.gas_limit),
+            },
+            Some(created),
+        ));
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(
+            host.storage[&U256::ZERO],
+            U256::from_be_bytes(created.into_word().0)
+        );
+        assert_eq!(host.storage[&U256::from(1)], U256::ZERO);
+        assert!(interp.stack.is_empty());
+    }
+
+    #[test]
+    fn create_syscall_in_static_call() {
+        let mut interp = riscv_interpreter("deploy_example", 1_000_000);
+        interp.is_static = true;
+
+        let result = run(&mut interp, &mut DummyHost::default());
+        assert_eq!(
+            result.result,
+            InstructionResult::StateChangeDuringStaticCall
+        );
+    }
+
+    #[test]
+    fn call_status_and_return_data() {
+        for (result, status) in [
+            (InstructionResult::Return, InstructionResult::Return),
+            (InstructionResult::Revert, InstructionResult::Revert),
+        ] {
+            let mut interp = riscv_interpreter("returndata_example", 1_000_000);
+            let mut host = DummyHost::default();
+            let inputs = run_call(&mut interp, &mut host);
+            let output = Bytes::from_static(b"output");
+            interp.insert_call_outcome(
+                &mut SharedMemory::new(),
+                CallOutcome::new(
+                    InterpreterResult {
+                        result,
+                        output: output.clone(),
+                        gas: Gas::new(inputs.gas_limit),
+                    },
+                    inputs.return_memory_offset.clone(),
+                ),
+            );
+
+            let result = run(&mut interp, &mut host);
+            assert_eq!(result.result, status);
+            assert_eq!(result.output, output);
+            let success = status == InstructionResult::Return;
+            assert_eq!(host.storage[&U256::ZERO], U256::from(success as u8));
+            assert_eq!(host.storage[&U256::from(1)], U256::from(output.len()));
+        }
+    }
+
+    #[test]
+    fn call_outcome_gas_and_refunds() {
+        for (result, erased, refunded) in [
+            (InstructionResult::Return, true, 50),
+            (InstructionResult::Revert, true, 0),
+            (InstructionResult::OutOfGas, false, 0),
+        ] {
+            let mut interp = riscv_interpreter("returndata_example", 1_000_000);
+            let mut host = DummyHost::default();
+            let inputs = run_call(&mut interp, &mut host);
+            // the output is copied to guest memory, the frame uses no shared memory.
+            let mut shared_memory = interp.take_memory();
+            assert_eq!(shared_memory.len(), 0);
+
+            let remaining = interp.gas.remaining();
+            let mut gas = Gas::new(inputs.gas_limit);
+            assert!(gas.record_cost(100));
+            gas.record_refund(50);
+            interp.insert_call_outcome(
+                &mut shared_memory,
+                CallOutcome::new(
+                    InterpreterResult {
+                        result,
+                        output: Bytes::from_static(b"output"),
+                        gas,
+                    },
+                    inputs.return_memory_offset.clone(),
+                ),
+            );
+            assert_eq!(shared_memory.len(), 0);
+
+            let returned = if erased { inputs.gas_limit - 100 } else { 0 };
+            assert_eq!(interp.gas.remaining(), remaining + returned);
+            assert_eq!(interp.gas.refunded(), refunded);
+        }
+    }
+
+    #[test]
+    fn return_data_copy_out_of_offset() {
+        let mut interp = riscv_interpreter("returndatacopy_example", 1_000_000);
+
+        let result = run(&mut interp, &mut DummyHost::default());
+        assert_eq!(result.result, InstructionResult::OutOfOffset);
+    }
+
+    #[test]
+    fn calldata_syscalls() {
+        let mut interp = riscv_interpreter("calldata_example", 1_000_000);
+        let input: Vec<u8> = (1..=36).collect();
+        interp.contract.input = Bytes::from(input.clone());
+        let mut host = DummyHost::default();
+
+        let result = run(&mut interp, &mut host);
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(host.storage[&U256::ZERO], U256::from(36));
+        assert_eq!(
+            host.storage[&U256::from(1)],
+            U256::from_be_slice(&input[1..33])
+        );
+        let mut copied = input[2..].to_vec();
+        copied.resize(40, 0);
+        assert_eq!(result.output, copied);
+    }
+
+    /// Runs `memory_example` storing at `addr` with the given DRAM size.
+    fn store_at(addr: u64, dram_size: u64) -> InterpreterResult {
+        let mut interp = riscv_interpreter("memory_example", 1_000_000);
+        interp.contract.input = U256::from(addr).to_be_bytes_vec().into();
+        let mut host = DummyHost::default();
+        host.env.cfg.riscv_dram_size = dram_size;
+        run(&mut interp, &mut host)
+    }
+
+    #[test]
+    fn memory_growth_is_charged() {
+        // inside the page of the image.
+        let image = store_at(0x8000_0800, RISCV_DRAM_SIZE);
+        assert_eq!(image.result, InstructionResult::Return);
+        assert_eq!(image.output[..], 0x1122334455667788u64.to_le_bytes());
+
+        // 256 pages past the end of the image.
+        let grown = store_at(0x8010_0000, RISCV_DRAM_SIZE);
+        assert_eq!(grown.result, InstructionResult::Return);
+        assert_eq!(grown.output, image.output);
+        assert_eq!(grown.gas.spent() - image.gas.spent(), 256 * gas::RISCV_PAGE);
+    }
+
+    #[test]
+    fn memory_below_image_is_charged() {
+        // `memory_example` has no absolute addresses, so it also runs linked higher.
+        let mut elf = vec![RISCV_MAGIC];
+        File::open("../../elf_test/memory_example")
+            .unwrap()
+            .read_to_end(&mut elf)
+            .unwrap();
+        // the entry point, and the virtual and physical address of the segment.
+        for offset in [1 + 24, 1 + 64 + 16, 1 + 64 + 24] {
+            let field = &mut elf[offset..offset + 8];
+            let addr = u64::from_le_bytes(field.try_into().unwrap()) + 0x30_0000;
+            field.copy_from_slice(&addr.to_le_bytes());
+        }
+        let bytecode = Bytecode::new_riscv(elf.into()).unwrap();
+        let mut interp = riscv_interpreter("memory_example", 1_000_000);
+        interp.contract.bytecode = bytecode.clone();
+        interp.contract.input = U256::from(0x8030_0800u64).to_be_bytes_vec().into();
+        let high = run(&mut interp, &mut DummyHost::default());
+        assert_eq!(high.result, InstructionResult::Return);
+
+        let low = store_at(0x8000_0800, RISCV_DRAM_SIZE);
+        assert_eq!(high.output, low.output);
+        assert_eq!(high.gas.spent() - low.gas.spent(), 0x300 * gas::RISCV_PAGE);
+
+        // without the gas for the pages, the memory is not allocated.
+        let mut interp = riscv_interpreter("memory_example", 0x300 * gas::RISCV_PAGE - 1);
+        interp.contract.bytecode = bytecode;
+        let result = run(&mut interp, &mut DummyHost::default());
+        assert_eq!(result.result, InstructionResult::OutOfGas);
+        assert!(interp.riscv_emulator.unwrap().memory.is_none());
+    }
+
+    #[test]
+    fn memory_is_bounded() {
+        let end = DRAM_BASE + RISCV_DRAM_SIZE;
+        for addr in [0, DRAM_BASE - 8, end - 4, end, u64::MAX - 4] {
+            let result = store_at(addr, RISCV_DRAM_SIZE);
+            assert_eq!(
+                result.result,
+                InstructionResult::RiscVOutOfBounds,
+                "{addr:#x}"
+            );
+        }
+        assert_eq!(
+            store_at(end, 2 * RISCV_DRAM_SIZE).result,
+            InstructionResult::Return
+        );
+
+        // the image itself does not fit.
+        assert_eq!(
+            store_at(0x8000_0800, 0).result,
+            InstructionResult::RiscVOutOfBounds
+        );
+    }
+
+    #[test]
+    fn traps() {
+        let mut interp = riscv_interpreter("trap_example", 1_000_000);
+        let result = run(&mut interp, &mut DummyHost::default());
+        assert_eq!(result.result, InstructionResult::RiscVUnknownSyscall);
+
+        let mut interp = riscv_interpreter("trap_example", 1_000_000);
+        interp.contract.input = Bytes::from_static(&[1]);
+        let result = run(&mut interp, &mut DummyHost::default());
+        assert_eq!(result.result, InstructionResult::RiscVInvalidInstruction);
+
+        assert_eq!(
+            exception_result(Exception::LoadAddressMisaligned),
+            InstructionResult::RiscVMisalignedAccess
+        );
+        assert_eq!(
+            exception_result(Exception::StoreAMOPageFault(0)),
+            InstructionResult::RiscVOutOfBounds
+        );
+        assert_eq!(
+            exception_result(Exception::Breakpoint),
+            InstructionResult::RiscVInvalidInstruction
+        );
+    }
+
+    #[test]
+    fn memory_access_decoding() {
+        let mut emu = Emulator::new();
+        emu.cpu.xregs.write(2, 0x8000_1000);
+        emu.cpu.xregs.write(8, 0x8000_2000);
+        let access = |inst| memory::memory_access(&emu, inst);
+
+        // ld a0, -8(s0)
+        assert_eq!(access(0xff843503), Some((0x8000_1ff8, 8)));
+        // sb a1, 3(s0)
+        assert_eq!(access(0x00b401a3), Some((0x8000_2003, 1)));
+        // amoadd.w a0, a1, (s0)
+        assert_eq!(access(0x00b4252f), Some((0x8000_2000, 4)));
+        // c.lw a0, 4(s0)
+        assert_eq!(access(0x4048), Some((0x8000_2004, 4)));
+        // c.sd a1, 40(s0)
+        assert_eq!(access(0xf40c), Some((0x8000_2028, 8)));
+        // c.sdsp ra, 8(sp)
+        assert_eq!(access(0xe406), Some((0x8000_1008, 8)));
+        // c.ldsp a0, 16(sp)
+        assert_eq!(access(0x6542), Some((0x8000_1010, 8)));
+        // c.lwsp a0, 196(sp)
+        assert_eq!(access(0x451e), Some((0x8000_10c4, 4)));
+        // c.swsp a1, 132(sp)
+        assert_eq!(access(0xc32e), Some((0x8000_1084, 4)));
+        // addi a0, a0, 1
+        assert_eq!(access(0x00150513), None);
+        // c.li a0, 0
+        assert_eq!(access(0x4501), None);
+    }
+
+    /// Resumes `interp` after its pending call returned `output` and runs it to the end.
+    #[cfg(feature = "serde")]
+    fn resume_call(
+        interp: &mut Interpreter,
+        inputs: &CallInputs,
+        output: &Bytes,
+        host: &mut DummyHost,
+    ) -> InterpreterResult {
+        interp.insert_call_outcome(
+            &mut SharedMemory::new(),
+            CallOutcome::new(
+                InterpreterResult {
+                    result: InstructionResult::Return,
+                    output: output.clone(),
+                    gas: Gas::new(inputs.gas_limit),
+                },
+                inputs.return_memory_offset.clone(),
+            ),
+        );
+        run(interp, host)
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_resumes_paused_call() {
+        let output = Bytes::from((1..=40).collect::<Vec<u8>>());
+        let mut interp = call_example(Syscall::Call, 1_000_000, false);
+        let inputs = run_call(&mut interp, &mut DummyHost::default());
+        let riscv = interp.riscv_emulator.as_ref().unwrap();
+        let pc = riscv.pc();
+        let registers: Vec<u64> = (0..32).map(|index| riscv.register(index)).collect();
+        let destiny = riscv.returned_data_destiny.clone();
+        assert!(destiny.is_some());
+
+        let serialized = bincode::serialize(&interp).unwrap();
+        let mut snapshot: Interpreter = bincode::deserialize(&serialized).unwrap();
+        let riscv = snapshot.riscv_emulator.as_ref().unwrap();
+        assert_eq!(riscv.pc(), pc);
+        assert!((0..32)
+            .map(|index| riscv.register(index))
+            .eq(registers.iter().copied()));
+        assert_eq!(riscv.returned_data_destiny, destiny);
+
+        let expected = resume_call(&mut interp, &inputs, &output, &mut DummyHost::default());
+        assert_eq!(expected.result, InstructionResult::Return);
+        // the output is copied to the 32-byte buffer of the call syscall.
+        assert_eq!(expected.output, output[..32]);
+        let result = resume_call(&mut snapshot, &inputs, &output, &mut DummyHost::default());
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_stores_non_zero_pages() {
+        // the image page and the page of the store, out of 257 pages.
+        let mut interp = riscv_interpreter("memory_example", 1_000_000);
+        interp.contract.input = U256::from(0x8010_0000u64).to_be_bytes_vec().into();
+        run(&mut interp, &mut DummyHost::default());
+
+        let json = serde_json::to_value(&interp.riscv_emulator).unwrap();
+        let pages = json["pages"].as_array().unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(
+            json["memory"]["size"].as_u64().unwrap(),
+            257 * memory::PAGE_SIZE
+        );
+
+        // a snapshot of a frame that has not run yet has no memory.
+        let interp = call_example(Syscall::Call, 1_000_000, false);
+        let json = serde_json::to_value(&interp.riscv_emulator).unwrap();
+        assert!(json["memory"].is_null());
+        assert_eq!(json["pages"], serde_json::json!([]));
+        let serialized = bincode::serialize(&interp).unwrap();
+        let mut snapshot: Interpreter = bincode::deserialize(&serialized).unwrap();
+        run_call(&mut snapshot, &mut DummyHost::default());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_rejects_invalid_pages() {
+        let mut interp = call_example(Syscall::Call, 1_000_000, false);
+        run_call(&mut interp, &mut DummyHost::default());
+
+        let mut json = serde_json::to_value(&interp.riscv_emulator).unwrap();
+        assert!(serde_json::from_value::<RVEmu>(json.clone()).is_ok());
+        json["pages"][0][0] = u64::MAX.into();
+        assert!(serde_json::from_value::<RVEmu>(json).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_bounds_restored_memory() {
+        let mut interp = call_example(Syscall::Call, 1_000_000, false);
+        run_call(&mut interp, &mut DummyHost::default());
+        let json = serde_json::to_value(&interp.riscv_emulator).unwrap();
+        let size = json["memory"]["size"].clone();
+
+        // only the memory the guest can access is allocated, not the capacity.
+        let mut large = json.clone();
+        large["memory"]["capacity"] = (1u64 << 62).into();
+        large["memory"]["limit"] = (1u64 << 62).into();
+        let riscv = serde_json::from_value::<RVEmu>(large).unwrap();
+        let restored = serde_json::to_value(&riscv).unwrap();
+        assert_eq!(restored["memory"]["capacity"], size);
+        assert_eq!(restored["pages"], json["pages"]);
+
+        // memory larger than a transaction can pay for is rejected.
+        let mut oversized = json;
+        for field in ["size", "capacity", "limit"] {
+            oversized["memory"][field] = (1u64 << 62).into();
+        }
+        assert!(serde_json::from_value::<RVEmu>(oversized).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+#[cfg(feature = "serde")]
+use crate::primitives::Bytes;
+use crate::primitives::RiscVBytecode;
+use rvemu::{bus::DRAM_BASE, emulator::Emulator};
+use std::vec;
+#[cfg(feature = "serde")]
+use std::vec::Vec;
+
+/// Size of the pages guest memory grows by.
+pub(crate) const PAGE_SIZE: u64 = 4096;
+
+/// Largest guest memory a snapshot can restore, 4 GiB. Growing memory that large costs
+/// over 400 million gas.
+#[cfg(feature = "serde")]
+const MAX_RESTORED_SIZE: u64 = 1 << 32;
+
+/// Guest memory of a RISC-V frame.
+///
+/// Memory is mapped from [`DRAM_BASE`] and the guest can access its first `size` bytes.
+/// It grows by whole pages as higher addresses are accessed, up to `limit` bytes. The
+/// emulator DRAM backing it doubles in capacity when it is full, so the host memory used
+/// is at most twice the size of the guest memory.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct GuestMemory {
+    /// Number of bytes the guest can access, a multiple of [`PAGE_SIZE`].
+    size: u64,
+    /// Number of bytes of the emulator DRAM.
+    capacity: u64,
+    /// Number of bytes the memory can grow to, a multiple of [`PAGE_SIZE`].
+    limit: u64,
+}
+
+impl GuestMemory {
+    /// Returns the memory the segments of `bytecode` are loaded into, and the number of its
+    /// pages without file bytes, including the pages below the segments, which are charged
+    /// like grown memory. Returns `None` if a segment is outside of the first `limit` bytes
+    /// of DRAM, `limit` being rounded down to a page.
+    ///
+    /// Nothing is allocated, so the pages can be charged before the memory is mapped with
+    /// [`GuestMemory::load`].
+    pub(crate) fn new(bytecode: &RiscVBytecode, limit: u64) -> Option<(Self, u64)> {
+        let limit = limit & !(PAGE_SIZE - 1);
+        let range = bytecode.memory_range();
+        let start = range.start.checked_sub(DRAM_BASE)?;
+        let mem_end = range.end - DRAM_BASE;
+        if mem_end > limit {
+            return None;
+        }
+        // the file bytes of a segment end before its memory does, so this does not overflow.
+        let file_end = bytecode
+            .segments()
+            .iter()
+            .map(|segment| segment.vaddr + segment.file_size - DRAM_BASE)
+            .max()
+            .unwrap_or(start);
+
+        let size = page_ceil(mem_end);
+        let memory = Self {
+            size,
+            capacity: size,
+            limit,
+        };
+        let file_pages = (page_ceil(file_end) - (start & !(PAGE_SIZE - 1))) / PAGE_SIZE;
+        Some((memory, size / PAGE_SIZE - file_pages))
+    }
+
+    /// Maps the memory image of `bytecode` into the DRAM of `emu` and sets the program
+    /// counter to the entry point.
+    ///
+    /// The memory must be the one returned by [`GuestMemory::new`] for `bytecode`, returns
+    /// `None` otherwise.
+    pub(crate) fn load(&self, emu: &mut Emulator, bytecode: &RiscVBytecode) -> Option<()> {
+        // the image is shared by the clones of the bytecode, only this copy is per frame.
+        let image = bytecode.image(self.size)?;
+        let start = (image.start - DRAM_BASE) as usize;
+        let mut dram = vec![0; self.size as usize];
+        dram[start..start + image.data.len()].copy_from_slice(&image.data);
+        emu.initialize_dram(dram);
+        emu.initialize_pc(bytecode.entry());
+        Some(())
+    }
+
+    /// Returns the number of pages the memory has to grow by for the guest to access
+    /// `len` bytes at address `addr`, or `None` if the range is outside of the limit.
+    #[inline]
+    pub(crate) fn pages_to_access(&self, addr: u64, len: u64) -> Option<u64> {
+        let end = addr.checked_sub(DRAM_BASE)?.checked_add(len)?;
+        if end > self.limit {
+            return None;
+        }
+        Some(page_ceil(end).saturating_sub(self.size) / PAGE_SIZE)
+    }
+
+    /// Grows the memory by `pages`, reallocating the emulator DRAM if it is full.
+    ///
+    /// The memory must stay inside the limit, see [`GuestMemory::pages_to_access`].
+    pub(crate) fn grow(&mut self, emu: &mut Emulator, pages: u64) {
+        self.size += pages * PAGE_SIZE;
+        if self.size <= self.capacity {
+            return;
+        }
+        let capacity = self.size.max(self.capacity * 2).min(self.limit);
+        let mut dram = vec![0; capacity as usize];
+        if let Ok(old) = emu
+            .cpu
+            .bus
+            .get_dram_slice(DRAM_BASE..DRAM_BASE + self.capacity)
+        {
+            dram[..old.len()].copy_from_slice(old);
+        }
+        emu.initialize_dram(dram);
+        self.capacity = capacity;
+    }
+
+    /// Returns the encoding of the instruction at `pc`, or `None` if it is not inside the
+    /// memory.
+    pub(crate) fn fetch(&self, emu: &mut Emulator, pc: u64) -> Option<u64> {
+        let low = self.read(emu, pc, 2)?;
+        if low & 0b11 != 0b11 {
+            return Some(low);
+        }
+        self.read(emu, pc, 4)
+    }
+
+    /// Reads a little-endian value of `len` bytes at `addr`, or `None` if it is not inside
+    /// the memory.
+    #[inline]
+    fn read(&self, emu: &mut Emulator, addr: u64, len: u64) -> Option<u64> {
+        if self.pages_to_access(addr, len)? != 0 {
+            return None;
+        }
+        let bytes = emu.cpu.bus.get_dram_slice(addr..addr + len).ok()?;
+        Some(
+            bytes
+                .iter()
+                .rev()
+                .fold(0, |value, byte| value << 8 | *byte as u64),
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl GuestMemory {
+    /// Returns the pages of the memory that are not all zeros, with their index.
+    pub(crate) fn pages(&self, emu: &mut Emulator) -> Vec<(u64, Bytes)> {
+        let Ok(dram) = emu.cpu.bus.get_dram_slice(DRAM_BASE..DRAM_BASE + self.size) else {
+            return Vec::new();
+        };
+        dram.chunks(PAGE_SIZE as usize)
+            .enumerate()
+            .filter(|(_, page)| page.iter().any(|byte| *byte != 0))
+            .map(|(index, page)| (index as u64, Bytes::copy_from_slice(page)))
+            .collect()
+    }
+
+    /// Allocates the emulator DRAM backing the memory and fills it with `pages`, as
+    /// returned by [`GuestMemory::pages`]. Returns the restored memory, the DRAM only spans
+    /// the memory the guest can access.
+    ///
+    /// Returns an error if the sizes of the memory are inconsistent, the memory is larger
+    /// than [`MAX_RESTORED_SIZE`], or a page is not a whole page inside the memory.
+    pub(crate) fn restore(
+        &self,
+        emu: &mut Emulator,
+        pages: &[(u64, Bytes)],
+    ) -> Result<Self, &'static str> {
+        if page_ceil(self.size) != self.size
+            || page_ceil(self.limit) != self.limit
+            || self.size > self.capacity
+            || self.capacity > self.limit
+        {
+            return Err("invalid RISC-V guest memory size");
+        }
+        if self.size > MAX_RESTORED_SIZE {
+            return Err("RISC-V guest memory too large to restore");
+        }
+        let mut dram = vec![0; self.size as usize];
+        for (index, page) in pages {
+            let start = index
+                .checked_mul(PAGE_SIZE)
+                .filter(|start| *start < self.size && page.len() as u64 == PAGE_SIZE)
+                .ok_or("invalid RISC-V guest memory page")? as usize;
+            dram[start..start + page.len()].copy_from_slice(page);
+        }
+        emu.initialize_dram(dram);
+        Ok(Self {
+            capacity: self.size,
+            ..*self
+        })
+    }
+}
+
+/// Returns the address and size of the guest memory accessed by `inst`, or `None` if it
+/// is not a load, store or atomic instruction.
+///
+/// Loads and stores through the stack pointer and the compressed registers of the C
+/// extension are decoded too.
+pub(crate) fn memory_access(emu: &Emulator, inst: u64) -> Option<(u64, u64)> {
+    let reg = |index: u64| emu.cpu.xregs.read(index);
+    let bits = |high: u64, low: u64| (inst >> low) & ((1 << (high - low + 1)) - 1);
+
+    if inst & 0b11 != 0b11 {
+        let funct3 = bits(15, 13);
+        // loads and stores of words are 4 bytes wide, all others 8 bytes.
+        let width = if funct3 & 0b11 == 0b10 { 4 } else { 8 };
+        return match (inst & 0b11, funct3) {
+            // c.lw and c.sw
+            (0b00, 2 | 6) => {
+                let offset = bits(12, 10) << 3 | bits(6, 6) << 2 | bits(5, 5) << 6;
+                Some((reg(bits(9, 7) + 8).wrapping_add(offset), width))
+            }
+            // c.fld, c.ld, c.fsd and c.sd
+            (0b00, 1 | 3 | 5 | 7) => {
+                let offset = bits(12, 10) << 3 | bits(6, 5) << 6;
+                Some((reg(bits(9, 7) + 8).wrapping_add(offset), width))
+            }
+            // c.lwsp
+            (0b10, 2) => {
+                let offset = bits(12, 12) << 5 | bits(6, 4) << 2 | bits(3, 2) << 6;
+                Some((reg(2).wrapping_add(offset), width))
+            }
+            // c.fldsp and c.ldsp
+            (0b10, 1 | 3) => {
+                let offset = bits(12, 12) << 5 | bits(6, 5) << 3 | bits(4, 2) << 6;
+                Some((reg(2).wrapping_add(offset), width))
+            }
+            // c.swsp
+            (0b10, 6) => {
+                let offset = bits(12, 9) << 2 | bits(8, 7) << 6;
+                Some((reg(2).wrapping_add(offset), width))
+            }
+            // c.fsdsp and c.sdsp
+            (0b10, 5 | 7) => {
+                let offset = bits(12, 10) << 3 | bits(9, 7) << 6;
+                Some((reg(2).wrapping_add(offset), width))
+            }
+            _ => None,
+        };
+    }
+
+    let base = reg(bits(19, 15));
+    let width = 1 << bits(13, 12);
+    let sign_extend = |value: u64| ((value << 52) as i64 >> 52) as u64;
+    match inst & 0x7f {
+        // LOAD and LOAD-FP
+        0x03 | 0x07 => Some((base.wrapping_add(sign_extend(bits(31, 20))), width)),
+        // STORE and STORE-FP
+        0x23 | 0x27 => {
+            let offset = sign_extend(bits(31, 25) << 5 | bits(11, 7));
+            Some((base.wrapping_add(offset), width))
+        }
+        // AMO
+        0x2f => Some((base, width)),
+        _ => None,
+    }
+}
+
+/// Rounds `size` up to a multiple of [`PAGE_SIZE`].
+#[inline]
+const fn page_ceil(size: u64) -> u64 {
+    size.next_multiple_of(PAGE_SIZE)
+}
@@ -0,0 +1,77 @@
+use super::{memory::GuestMemory, RVEmu};
+use crate::primitives::{Bytes, SpecId};
+use core::{array, cell::RefCell, ops::Range};
+use rvemu::emulator::Emulator;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::vec::Vec;
+
+/// Serialized state of a [`RVEmu`].
+///
+/// Only the state a contract can observe between instructions is kept: the program
+/// counter, the integer registers and guest memory. The emulator DRAM is sparse, only the
+/// pages that are not all zeros are stored. Contracts target `riscv64imac`, so the
+/// floating-point registers are not part of the state.
+#[derive(Serialize, Deserialize)]
+struct RVEmuState {
+    pc: u64,
+    registers: [u64; 32],
+    memory: Option<GuestMemory>,
+    pages: Vec<(u64, Bytes)>,
+    returned_data_destiny: Option<Range<u64>>,
+    spec_id: Option<SpecId>,
+}
+
+impl Serialize for RVEmu {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut emu = self.emu.borrow_mut();
+        let state = RVEmuState {
+            pc: emu.cpu.pc,
+            registers: array::from_fn(|index| emu.cpu.xregs.read(index as u64)),
+            memory: self.memory,
+            pages: self
+                .memory
+                .map(|memory| memory.pages(&mut emu))
+                .unwrap_or_default(),
+            returned_data_destiny: self.returned_data_destiny.clone(),
+            spec_id: self.spec_id,
+        };
+        state.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RVEmu {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let state = RVEmuState::deserialize(deserializer)?;
+        let mut emu = Emulator::new();
+        let memory = match &state.memory {
+            Some(memory) => Some(
+                memory
+                    .restore(&mut emu, &state.pages)
+                    .map_err(de::Error::custom)?,
+            ),
+            None if !state.pages.is_empty() => {
+                return Err(de::Error::custom(
+                    "RISC-V memory pages without guest memory",
+                ))
+            }
+            None => None,
+        };
+        for (index, value) in state.registers.into_iter().enumerate() {
+            emu.cpu.xregs.write(index as u64, value);
+        }
+        emu.cpu.pc = state.pc;
+
+        Ok(Self {
+            emu: RefCell::new(emu),
+            memory,
+            returned_data_destiny: state.returned_data_destiny,
+            spec_id: state.spec_id,
+        })
+    }
+}
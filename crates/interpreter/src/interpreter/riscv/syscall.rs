@@ -0,0 +1,188 @@
+/// Syscalls a RISC-V contract can make with `ecall`.
+///
+/// The syscall number is passed in `t0` and arguments in `a0..a7`. Words are passed
+/// in four consecutive registers as little-endian 64-bit limbs, least significant limb
+/// first, so a word "in `a0..a3`" has its low 64 bits in `a0`. Addresses are passed and
+/// returned as words holding the address in the low 160 bits, the same way the EVM
+/// keeps them on the stack. Values sent with calls and creates don't fit in the registers
+/// left, they are passed as pointers to 32-byte big-endian words in guest memory. Results
+/// are written back to the argument registers, and the call syscalls resume with `1` in
+/// `a0..a3` if the call succeeded and `0` otherwise.
+///
+/// Apart from the original `Return`, `SLoad`, `SStore`, `Call` and `Revert` syscalls,
+/// and `Precompile` which has no EVM counterpart, the number of a syscall is the opcode
+/// of the EVM instruction it mirrors, and it is
+/// charged the same gas and requires the same spec as that instruction. Like their
+/// opcodes, the gas limit passed to the call syscalls is capped to all but one 64th of
+/// the remaining gas.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Syscall {
+    /// Returns `a1` bytes of guest memory at `a0` as output.
+    Return = 0,
+    /// Loads the storage word with key `a0..a3` into `a0..a3`.
+    SLoad = 1,
+    /// Stores word `a4..a7` under key `a0..a3`.
+    SStore = 2,
+    /// Calls the 20-byte address at guest pointer `a1` with gas limit `a0`, the 32-byte
+    /// value at guest pointer `a2`, input of `a4` bytes at `a3`, and copies up to `a6` bytes
+    /// of output to `a5` when resumed.
+    Call = 3,
+    /// Reverts with `a1` bytes of guest memory at `a0` as output.
+    Revert = 4,
+    /// Runs the precompile at the 20-byte address at guest pointer `a0` without a call
+    /// frame, with input of `a2` bytes at `a1` and gas limit `a3`, capped to the remaining
+    /// gas, and copies up to `a5` bytes of output to `a4`. Writes `1` to `a0..a3` if it
+    /// succeeded and `0` otherwise, the whole output is read with the return data
+    /// syscalls. A failed precompile consumes its gas limit, and an address without a
+    /// precompile fails without consuming gas.
+    Precompile = 5,
+    /// Keccak-256 hash of `a1` bytes of guest memory at `a0` in `a0..a3`.
+    Keccak256 = 0x20,
+    /// Address of the executing contract in `a0..a3`.
+    Address = 0x30,
+    /// Balance of address `a0..a3` in `a0..a3`.
+    Balance = 0x31,
+    /// Transaction origin in `a0..a3`.
+    Origin = 0x32,
+    /// Caller address in `a0..a3`.
+    Caller = 0x33,
+    /// Value sent with the call in `a0..a3`.
+    CallValue = 0x34,
+    /// Calldata word at offset `a0..a3` in `a0..a3`, padded with zeros past the end of
+    /// the calldata.
+    CallDataLoad = 0x35,
+    /// Calldata size in `a0..a3`.
+    CallDataSize = 0x36,
+    /// Copies `a2` bytes of calldata, starting at offset `a1`, to guest memory at `a0`.
+    /// Bytes past the end of the calldata are zero.
+    CallDataCopy = 0x37,
+    /// Effective gas price of the transaction in `a0..a3`.
+    GasPrice = 0x3A,
+    /// Code size of address `a0..a3` in `a0..a3`.
+    ExtCodeSize = 0x3B,
+    /// Copies `a6` bytes of the code of address `a0..a3`, starting at code offset `a5`,
+    /// to guest memory at `a4`. Bytes past the end of the code are zero.
+    ExtCodeCopy = 0x3C,
+    /// Size of the output of the last call or create in `a0..a3`.
+    ReturnDataSize = 0x3D,
+    /// Copies `a2` bytes of the output of the last call or create, starting at offset `a1`,
+    /// to guest memory at `a0`. Fails if the range is past the end of the output.
+    ReturnDataCopy = 0x3E,
+    /// Code hash of address `a0..a3` in `a0..a3`.
+    ExtCodeHash = 0x3F,
+    /// Hash of block number `a0..a3` in `a0..a3`.
+    BlockHash = 0x40,
+    /// Block beneficiary in `a0..a3`.
+    Coinbase = 0x41,
+    /// Block timestamp in `a0..a3`.
+    Timestamp = 0x42,
+    /// Block number in `a0..a3`.
+    Number = 0x43,
+    /// Block prevrandao, or difficulty before the merge, in `a0..a3`.
+    PrevRandao = 0x44,
+    /// Block gas limit in `a0..a3`.
+    GasLimit = 0x45,
+    /// Chain id in `a0..a3`.
+    ChainId = 0x46,
+    /// Balance of the executing contract in `a0..a3`.
+    SelfBalance = 0x47,
+    /// Block base fee in `a0..a3`.
+    BaseFee = 0x48,
+    /// Versioned blob hash at index `a0..a3` of the transaction in `a0..a3`, zero if there
+    /// is no such blob.
+    BlobHash = 0x49,
+    /// Block blob base fee in `a0..a3`.
+    BlobBaseFee = 0x4A,
+    /// Loads the transient storage word with key `a0..a3` into `a0..a3`.
+    TLoad = 0x5C,
+    /// Stores word `a4..a7` under key `a0..a3` in transient storage.
+    TStore = 0x5D,
+    /// Emits a log with `a1` bytes of data at `a0` and no topics.
+    Log0 = 0xA0,
+    /// Emits a log with `a1` bytes of data at `a0` and the 32-byte topic at `a2`.
+    Log1 = 0xA1,
+    /// Emits a log with `a1` bytes of data at `a0` and the 32-byte topics at `a2` and `a3`.
+    Log2 = 0xA2,
+    /// Emits a log with `a1` bytes of data at `a0` and the 32-byte topics at `a2..a4`.
+    Log3 = 0xA3,
+    /// Emits a log with `a1` bytes of data at `a0` and the 32-byte topics at `a2..a5`.
+    Log4 = 0xA4,
+    /// Creates a contract with the 32-byte value at guest pointer `a0` and the `a2` bytes of
+    /// initcode at `a1`, and writes the new address, or zero if the creation failed, to
+    /// `a0..a3` when resumed.
+    Create = 0xF0,
+    /// Calls the code of the 20-byte address at guest pointer `a1` in the context of the
+    /// executing contract, with gas limit `a0`, the 32-byte value at guest pointer `a2`,
+    /// input of `a4` bytes at `a3`, and copies up to `a6` bytes of output to `a5` when
+    /// resumed.
+    CallCode = 0xF2,
+    /// Calls the code of the 20-byte address at guest pointer `a1` keeping the caller and
+    /// value of the executing contract, with gas limit `a0`, input of `a3` bytes at `a2`,
+    /// and copies up to `a5` bytes of output to `a4` when resumed.
+    DelegateCall = 0xF4,
+    /// Like [`Syscall::Create`], with the address derived from salt `a3..a6`.
+    Create2 = 0xF5,
+    /// Calls the 20-byte address at guest pointer `a1` without allowing state changes,
+    /// with gas limit `a0`, input of `a3` bytes at `a2`, and copies up to `a5` bytes of
+    /// output to `a4` when resumed.
+    StaticCall = 0xFA,
+    /// Destroys the executing contract and sends its balance to address `a0..a3`. Since
+    /// Cancun, the contract is only destroyed if it was created in the same transaction.
+    SelfDestruct = 0xFF,
+}
+
+impl Syscall {
+    /// Returns the syscall with the given number.
+    pub const fn from_u64(number: u64) -> Option<Self> {
+        Some(match number {
+            0 => Self::Return,
+            1 => Self::SLoad,
+            2 => Self::SStore,
+            3 => Self::Call,
+            4 => Self::Revert,
+            5 => Self::Precompile,
+            0x20 => Self::Keccak256,
+            0x30 => Self::Address,
+            0x31 => Self::Balance,
+            0x32 => Self::Origin,
+            0x33 => Self::Caller,
+            0x34 => Self::CallValue,
+            0x35 => Self::CallDataLoad,
+            0x36 => Self::CallDataSize,
+            0x37 => Self::CallDataCopy,
+            0x3A => Self::GasPrice,
+            0x3B => Self::ExtCodeSize,
+            0x3C => Self::ExtCodeCopy,
+            0x3D => Self::ReturnDataSize,
+            0x3E => Self::ReturnDataCopy,
+            0x3F => Self::ExtCodeHash,
+            0x40 => Self::BlockHash,
+            0x41 => Self::Coinbase,
+            0x42 => Self::Timestamp,
+            0x43 => Self::Number,
+            0x44 => Self::PrevRandao,
+            0x45 => Self::GasLimit,
+            0x46 => Self::ChainId,
+            0x47 => Self::SelfBalance,
+            0x48 => Self::BaseFee,
+            0x49 => Self::BlobHash,
+            0x4A => Self::BlobBaseFee,
+            0x5C => Self::TLoad,
+            0x5D => Self::TStore,
+            0xA0 => Self::Log0,
+            0xA1 => Self::Log1,
+            0xA2 => Self::Log2,
+            0xA3 => Self::Log3,
+            0xA4 => Self::Log4,
+            0xF0 => Self::Create,
+            0xF2 => Self::CallCode,
+            0xF4 => Self::DelegateCall,
+            0xF5 => Self::Create2,
+            0xFA => Self::StaticCall,
+            0xFF => Self::SelfDestruct,
+            _ => return None,
+        })
+    }
+}
@@ -3,10 +3,14 @@ use crate::{
 };
 
 use super::Interpreter;
+#[cfg(feature = "riscv")]
+use crate::RVEmu;
 use revm_primitives::Bytes;
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "riscv")]
+use std::boxed::Box;
 use std::fmt;
 
 impl Serialize for Interpreter {
@@ -30,6 +34,8 @@ impl Serialize for Interpreter {
         state.serialize_field("return_data_buffer", &self.return_data_buffer)?;
         state.serialize_field("is_static", &self.is_static)?;
         state.serialize_field("next_action", &self.next_action)?;
+        #[cfg(feature = "riscv")]
+        state.serialize_field("riscv_emulator", &self.riscv_emulator)?;
         state.end()
     }
 }
@@ -57,6 +63,8 @@ impl<'de> Deserialize<'de> for Interpreter {
             ReturnDataBuffer,
             IsStatic,
             NextAction,
+            #[cfg(feature = "riscv")]
+            RiscvEmulator,
         }
 
         #[allow(clippy::too_many_arguments)]
@@ -74,6 +82,7 @@ impl<'de> Deserialize<'de> for Interpreter {
             return_data_buffer: Bytes,
             is_static: bool,
             next_action: InterpreterAction,
+            #[cfg(feature = "riscv")] riscv_emulator: Option<Box<RVEmu>>,
         ) -> Result<Interpreter, &'static str> {
             // Reconstruct the instruction pointer from usize
             if program_counter < 0 || program_counter >= bytecode.len() as isize {
@@ -98,6 +107,8 @@ impl<'de> Deserialize<'de> for Interpreter {
                 return_data_buffer,
                 is_static,
                 next_action,
+                #[cfg(feature = "riscv")]
+                riscv_emulator,
             })
         }
 
@@ -132,6 +143,8 @@ impl<'de> Deserialize<'de> for Interpreter {
                 extract_field!(return_data_buffer, 10);
                 extract_field!(is_static, 11);
                 extract_field!(next_action, 12);
+                #[cfg(feature = "riscv")]
+                extract_field!(riscv_emulator, 13);
                 rebuild_interp(
                     instruction_pointer,
                     gas,
@@ -146,6 +159,8 @@ impl<'de> Deserialize<'de> for Interpreter {
                     return_data_buffer,
                     is_static,
                     next_action,
+                    #[cfg(feature = "riscv")]
+                    riscv_emulator,
                 )
                 .map_err(de::Error::custom)
             }
@@ -154,6 +169,9 @@ impl<'de> Deserialize<'de> for Interpreter {
             where
                 V: MapAccess<'de>,
             {
+                // frames without a RISC-V emulator may leave it out.
+                #[cfg(feature = "riscv")]
+                let mut riscv_emulator = None;
                 macro_rules! parse_map {
                     ( $(($enum:pat, $var_name:ident)),* ) => {
                         $(
@@ -166,6 +184,10 @@ impl<'de> Deserialize<'de> for Interpreter {
                                         $var_name = Some(map.next_value()?);
                                     }
                                 )*
+                                #[cfg(feature = "riscv")]
+                                InterpreterFields::RiscvEmulator => {
+                                    riscv_emulator = map.next_value()?;
+                                }
                             }
                         }
                         $(
@@ -203,6 +225,8 @@ impl<'de> Deserialize<'de> for Interpreter {
                     return_data_buffer,
                     is_static,
                     next_action,
+                    #[cfg(feature = "riscv")]
+                    riscv_emulator,
                 )
                 .map_err(de::Error::custom)
             }
@@ -222,6 +246,8 @@ impl<'de> Deserialize<'de> for Interpreter {
             "return_data_buffer",
             "is_static",
             "next_action",
+            #[cfg(feature = "riscv")]
+            "riscv_emulator",
         ];
 
         deserializer.deserialize_struct("Interpreter", FIELDS, InterpreterVisitor)
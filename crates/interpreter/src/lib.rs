@@ -35,6 +35,8 @@ pub use interpreter::{
     analysis, num_words, Contract, Interpreter, InterpreterResult, SharedMemory, Stack,
     EMPTY_SHARED_MEMORY, STACK_LIMIT,
 };
+#[cfg(feature = "riscv")]
+pub use interpreter::{RVEmu, RiscVHooks, Syscall};
 pub use interpreter_action::{
     CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome, CreateScheme,
     EOFCreateInput, EOFCreateOutcome, InterpreterAction,
@@ -1,8 +1,12 @@
 pub mod eof;
 pub mod legacy;
+#[cfg(feature = "riscv")]
+pub mod riscv;
 
 pub use eof::Eof;
 pub use legacy::{JumpTable, LegacyAnalyzedBytecode};
+#[cfg(feature = "riscv")]
+pub use riscv::{ElfSegment, RiscVBytecode, RiscVDecodeError, RiscVImage, RISCV_MAGIC};
 
 use crate::{keccak256, Bytes, B256, KECCAK_EMPTY};
 
@@ -16,6 +20,9 @@ pub enum Bytecode {
     LegacyAnalyzed(LegacyAnalyzedBytecode),
     /// Ethereum Object Format
     Eof(Eof),
+    /// RISC-V ELF contract.
+    #[cfg(feature = "riscv")]
+    RiscV(RiscVBytecode),
 }
 
 impl Default for Bytecode {
@@ -66,6 +73,49 @@ impl Bytecode {
         matches!(self, Self::Eof(_))
     }
 
+    /// Return reference to the RISC-V bytecode if bytecode is RISC-V.
+    #[cfg(feature = "riscv")]
+    #[inline]
+    pub const fn riscv(&self) -> Option<&RiscVBytecode> {
+        match self {
+            Self::RiscV(riscv) => Some(riscv),
+            _ => None,
+        }
+    }
+
+    /// Return true if bytecode is RISC-V.
+    #[cfg(feature = "riscv")]
+    #[inline]
+    pub const fn is_riscv(&self) -> bool {
+        matches!(self, Self::RiscV(_))
+    }
+
+    /// Creates a new RISC-V [`Bytecode`] from raw bytes starting with [`RISCV_MAGIC`].
+    #[cfg(feature = "riscv")]
+    #[inline]
+    pub fn new_riscv(bytecode: Bytes) -> Result<Self, RiscVDecodeError> {
+        RiscVBytecode::decode(bytecode).map(Self::RiscV)
+    }
+
+    /// Returns the bytecode as it is executed, depending on whether RISC-V execution is
+    /// enabled.
+    ///
+    /// With RISC-V execution, legacy bytecode starting with [`RISCV_MAGIC`] that decodes as
+    /// a RISC-V ELF is RISC-V code, however it was stored. Without it, RISC-V code is legacy
+    /// bytecode like on mainnet.
+    #[cfg(feature = "riscv")]
+    pub fn resolve_riscv(self, riscv_enabled: bool) -> Self {
+        match self {
+            Self::RiscV(riscv) if !riscv_enabled => Self::new_raw(riscv.raw().clone()),
+            Self::LegacyRaw(_) | Self::LegacyAnalyzed(_)
+                if riscv_enabled && self.original_byte_slice().first() == Some(&RISCV_MAGIC) =>
+            {
+                Self::new_riscv(self.original_bytes()).unwrap_or(self)
+            }
+            bytecode => bytecode,
+        }
+    }
+
     /// Creates a new raw [`Bytecode`].
     #[inline]
     pub fn new_raw(bytecode: Bytes) -> Self {
@@ -92,7 +142,8 @@ impl Bytecode {
 
     /// Returns a reference to the bytecode.
     ///
-    /// In case of EOF this will be the first code section.
+    /// In case of EOF this will be the first code section. In case of RISC-V this
+    /// will be the raw bytes.
     #[inline]
     pub fn bytecode(&self) -> &Bytes {
         match self {
@@ -102,6 +153,8 @@ impl Bytecode {
                 .body
                 .code(0)
                 .expect("Valid EOF has at least one code section"),
+            #[cfg(feature = "riscv")]
+            Self::RiscV(riscv) => riscv.raw(),
         }
     }
 
@@ -117,6 +170,8 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes.clone(),
             Self::LegacyAnalyzed(analyzed) => analyzed.bytecode().clone(),
             Self::Eof(eof) => eof.raw().clone(),
+            #[cfg(feature = "riscv")]
+            Self::RiscV(riscv) => riscv.raw().clone(),
         }
     }
 
@@ -127,6 +182,8 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes,
             Self::LegacyAnalyzed(analyzed) => analyzed.bytecode(),
             Self::Eof(eof) => eof.raw(),
+            #[cfg(feature = "riscv")]
+            Self::RiscV(riscv) => riscv.raw(),
         }
     }
 
@@ -137,6 +194,8 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes.clone(),
             Self::LegacyAnalyzed(analyzed) => analyzed.original_bytes(),
             Self::Eof(eof) => eof.raw().clone(),
+            #[cfg(feature = "riscv")]
+            Self::RiscV(riscv) => riscv.raw().clone(),
         }
     }
 
@@ -147,6 +206,8 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes,
             Self::LegacyAnalyzed(analyzed) => analyzed.original_byte_slice(),
             Self::Eof(eof) => eof.raw(),
+            #[cfg(feature = "riscv")]
+            Self::RiscV(riscv) => riscv.raw(),
         }
     }
 
@@ -157,6 +218,8 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes.len(),
             Self::LegacyAnalyzed(analyzed) => analyzed.original_len(),
             Self::Eof(eof) => eof.size(),
+            #[cfg(feature = "riscv")]
+            Self::RiscV(riscv) => riscv.raw().len(),
         }
     }
 
@@ -0,0 +1,588 @@
+use crate::Bytes;
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+use once_cell::race::OnceBox;
+use std::{boxed::Box, sync::Arc, vec, vec::Vec};
+
+/// Marker byte prepended to the ELF image of RISC-V contract code.
+pub const RISCV_MAGIC: u8 = 0xFF;
+
+/// ELF file identification bytes.
+const ELF_MAGIC: [u8; 4] = [0x7F, b'E', b'L', b'F'];
+/// 64-bit ELF class.
+const ELFCLASS64: u8 = 2;
+/// Little-endian data encoding.
+const ELFDATA2LSB: u8 = 1;
+/// Current ELF version.
+const EV_CURRENT: u8 = 1;
+/// Executable file type.
+const ET_EXEC: u16 = 2;
+/// RISC-V machine type.
+const EM_RISCV: u16 = 0xF3;
+/// Size of the ELF64 file header.
+const EHDR_SIZE: usize = 64;
+/// Size of an ELF64 program header entry.
+const PHDR_SIZE: usize = 56;
+/// Size of an ELF64 section header entry.
+const SHDR_SIZE: usize = 64;
+/// Loadable program segment.
+const PT_LOAD: u32 = 1;
+/// Executable segment flag.
+const PF_X: u32 = 1;
+/// Section that occupies no space in the file.
+const SHT_NOBITS: u32 = 8;
+
+/// RISC-V contract code.
+///
+/// Raw bytes are [`RISCV_MAGIC`] followed by a 64-bit little-endian RISC-V executable ELF.
+/// Loadable segments are decoded and checked on creation so the interpreter can map them
+/// without further validation.
+///
+/// The memory image the segments are loaded from is built the first time it is needed and
+/// shared by the clones of this value. The EVM context caches RISC-V code by code hash, so
+/// all the calls to a contract share its image, see `InnerEvmContext::resolve_riscv`.
+///
+/// Only the raw bytes are serialized, they are decoded again on deserialization.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Bytes", into = "Bytes")
+)]
+pub struct RiscVBytecode {
+    /// Raw bytes including the [`RISCV_MAGIC`] prefix.
+    raw: Bytes,
+    /// Entry point virtual address.
+    entry: u64,
+    /// Loadable segments.
+    segments: Vec<ElfSegment>,
+    /// Memory image of the segments, see [`RiscVBytecode::image`].
+    image: Arc<OnceBox<RiscVImage>>,
+}
+
+// Implement PartialEq and Hash manually because the image is derived from the raw bytes.
+impl PartialEq for RiscVBytecode {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl Eq for RiscVBytecode {}
+
+impl TryFrom<Bytes> for RiscVBytecode {
+    type Error = RiscVDecodeError;
+
+    #[inline]
+    fn try_from(raw: Bytes) -> Result<Self, Self::Error> {
+        Self::decode(raw)
+    }
+}
+
+impl From<RiscVBytecode> for Bytes {
+    #[inline]
+    fn from(bytecode: RiscVBytecode) -> Self {
+        bytecode.raw
+    }
+}
+
+impl Hash for RiscVBytecode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.raw.hash(state);
+    }
+}
+
+/// Initial memory of a RISC-V program.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RiscVImage {
+    /// Virtual address of the first byte of `data`, the lowest segment address.
+    pub start: u64,
+    /// File bytes of the segments at their offset from `start`, zero in between. Ends with
+    /// the file bytes of the last segment.
+    pub data: Vec<u8>,
+}
+
+/// Loadable segment of a RISC-V ELF.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElfSegment {
+    /// Offset of the segment in the ELF file.
+    pub offset: u64,
+    /// Virtual address the segment is loaded at.
+    pub vaddr: u64,
+    /// Number of bytes copied from the file.
+    pub file_size: u64,
+    /// Number of bytes the segment occupies in memory, the tail is zeroed.
+    pub mem_size: u64,
+    /// Segment permission flags.
+    pub flags: u32,
+}
+
+impl ElfSegment {
+    /// Returns true if the segment is executable.
+    #[inline]
+    pub const fn is_executable(&self) -> bool {
+        self.flags & PF_X != 0
+    }
+
+    /// Returns true if `addr` is inside the memory range of the segment.
+    #[inline]
+    pub const fn contains(&self, addr: u64) -> bool {
+        addr >= self.vaddr && addr - self.vaddr < self.mem_size
+    }
+}
+
+impl RiscVBytecode {
+    /// Decode RISC-V bytecode from raw bytes starting with [`RISCV_MAGIC`].
+    pub fn decode(raw: Bytes) -> Result<Self, RiscVDecodeError> {
+        let Some((&RISCV_MAGIC, elf)) = raw.split_first() else {
+            return Err(RiscVDecodeError::InvalidMagic);
+        };
+        let (entry, segments) = decode_elf(elf)?;
+        Ok(Self {
+            raw,
+            entry,
+            segments,
+            image: Arc::default(),
+        })
+    }
+
+    /// Returns the raw bytes including the [`RISCV_MAGIC`] prefix.
+    #[inline]
+    pub fn raw(&self) -> &Bytes {
+        &self.raw
+    }
+
+    /// Returns the ELF image without the [`RISCV_MAGIC`] prefix.
+    #[inline]
+    pub fn elf(&self) -> &[u8] {
+        &self.raw[1..]
+    }
+
+    /// Returns the entry point virtual address.
+    #[inline]
+    pub fn entry(&self) -> u64 {
+        self.entry
+    }
+
+    /// Returns the loadable segments.
+    #[inline]
+    pub fn segments(&self) -> &[ElfSegment] {
+        &self.segments
+    }
+
+    /// Returns the file bytes of the segment.
+    #[inline]
+    pub fn segment_data(&self, segment: &ElfSegment) -> &[u8] {
+        let start = segment.offset as usize;
+        &self.elf()[start..start + segment.file_size as usize]
+    }
+
+    /// Returns the address range the segments occupy in memory, from the lowest segment
+    /// address to the end of the highest segment.
+    #[inline]
+    pub fn memory_range(&self) -> Range<u64> {
+        let start = self
+            .segments
+            .iter()
+            .map(|s| s.vaddr)
+            .min()
+            .unwrap_or_default();
+        // the end of a segment does not overflow, it is checked when decoding.
+        let end = self
+            .segments
+            .iter()
+            .map(|s| s.vaddr + s.mem_size)
+            .max()
+            .unwrap_or_default();
+        start..end
+    }
+
+    /// Returns the memory image of the segments, building it on first use, or `None` if it
+    /// is longer than `max_len` bytes.
+    ///
+    /// The image spans the file bytes of all segments, from the lowest segment address, so
+    /// its length is checked before anything is allocated.
+    pub fn image(&self, max_len: u64) -> Option<&RiscVImage> {
+        let start = self.memory_range().start;
+        let end = self
+            .segments
+            .iter()
+            .map(|s| s.vaddr + s.file_size)
+            .max()
+            .unwrap_or_default();
+        if end - start > max_len {
+            return None;
+        }
+        Some(self.image.get_or_init(|| {
+            let mut data = vec![0; (end - start) as usize];
+            for segment in &self.segments {
+                let offset = (segment.vaddr - start) as usize;
+                let bytes = self.segment_data(segment);
+                data[offset..offset + bytes.len()].copy_from_slice(bytes);
+            }
+            Box::new(RiscVImage { start, data })
+        }))
+    }
+}
+
+/// Decodes the ELF header, program headers and section headers, returning the entry
+/// point and the loadable segments.
+fn decode_elf(elf: &[u8]) -> Result<(u64, Vec<ElfSegment>), RiscVDecodeError> {
+    if elf.len() < EHDR_SIZE {
+        return Err(RiscVDecodeError::MissingInput);
+    }
+    if elf[..4] != ELF_MAGIC {
+        return Err(RiscVDecodeError::InvalidElfMagic);
+    }
+    if elf[4] != ELFCLASS64 {
+        return Err(RiscVDecodeError::InvalidClass);
+    }
+    if elf[5] != ELFDATA2LSB {
+        return Err(RiscVDecodeError::InvalidEndianness);
+    }
+    if elf[6] != EV_CURRENT {
+        return Err(RiscVDecodeError::InvalidVersion);
+    }
+    if read_u16(elf, 16) != ET_EXEC {
+        return Err(RiscVDecodeError::NotExecutable);
+    }
+    if read_u16(elf, 18) != EM_RISCV {
+        return Err(RiscVDecodeError::InvalidMachine);
+    }
+
+    let entry = read_u64(elf, 24);
+    let phoff = read_u64(elf, 32);
+    let shoff = read_u64(elf, 40);
+    let phentsize = read_u16(elf, 54) as usize;
+    let phnum = read_u16(elf, 56) as usize;
+    let shentsize = read_u16(elf, 58) as usize;
+    let shnum = read_u16(elf, 60) as usize;
+
+    // program headers
+    if phnum == 0 {
+        return Err(RiscVDecodeError::NoLoadableSegments);
+    }
+    if phentsize != PHDR_SIZE {
+        return Err(RiscVDecodeError::InvalidProgramHeaderSize);
+    }
+    let program_headers =
+        table(elf, phoff, phnum, PHDR_SIZE).ok_or(RiscVDecodeError::ProgramHeadersOutOfBounds)?;
+
+    let mut segments = Vec::new();
+    for header in program_headers.chunks_exact(PHDR_SIZE) {
+        if read_u32(header, 0) != PT_LOAD {
+            continue;
+        }
+        let segment = ElfSegment {
+            flags: read_u32(header, 4),
+            offset: read_u64(header, 8),
+            vaddr: read_u64(header, 16),
+            file_size: read_u64(header, 32),
+            mem_size: read_u64(header, 40),
+        };
+        if segment.file_size > segment.mem_size {
+            return Err(RiscVDecodeError::InvalidSegmentSize);
+        }
+        if !in_file(elf, segment.offset, segment.file_size) {
+            return Err(RiscVDecodeError::SegmentOutOfBounds);
+        }
+        if segment.vaddr.checked_add(segment.mem_size).is_none() {
+            return Err(RiscVDecodeError::SegmentOutOfBounds);
+        }
+        if segments.iter().any(|other: &ElfSegment| {
+            segment.vaddr < other.vaddr + other.mem_size
+                && other.vaddr < segment.vaddr + segment.mem_size
+        }) {
+            return Err(RiscVDecodeError::OverlappingSegments);
+        }
+        segments.push(segment);
+    }
+    if segments.is_empty() {
+        return Err(RiscVDecodeError::NoLoadableSegments);
+    }
+
+    // section headers are optional, but must be well formed when present.
+    if shnum != 0 {
+        if shentsize != SHDR_SIZE {
+            return Err(RiscVDecodeError::InvalidSectionHeaderSize);
+        }
+        let section_headers = table(elf, shoff, shnum, SHDR_SIZE)
+            .ok_or(RiscVDecodeError::SectionHeadersOutOfBounds)?;
+        for header in section_headers.chunks_exact(SHDR_SIZE) {
+            if read_u32(header, 4) == SHT_NOBITS {
+                continue;
+            }
+            let offset = read_u64(header, 24);
+            let size = read_u64(header, 32);
+            if !in_file(elf, offset, size) {
+                return Err(RiscVDecodeError::SectionOutOfBounds);
+            }
+        }
+    }
+
+    if !segments
+        .iter()
+        .any(|segment| segment.is_executable() && segment.contains(entry))
+    {
+        return Err(RiscVDecodeError::InvalidEntryPoint);
+    }
+
+    Ok((entry, segments))
+}
+
+/// Returns the bytes of a table of `num` entries of `entry_size` bytes at `offset`.
+#[inline]
+fn table(elf: &[u8], offset: u64, num: usize, entry_size: usize) -> Option<&[u8]> {
+    let start = usize::try_from(offset).ok()?;
+    elf.get(start..start.checked_add(num * entry_size)?)
+}
+
+/// Returns true if the `offset..offset + size` range is inside the file.
+#[inline]
+fn in_file(elf: &[u8], offset: u64, size: u64) -> bool {
+    offset
+        .checked_add(size)
+        .is_some_and(|end| end <= elf.len() as u64)
+}
+
+#[inline]
+fn read_u16(input: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(input[offset..offset + 2].try_into().unwrap())
+}
+
+#[inline]
+fn read_u32(input: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(input[offset..offset + 4].try_into().unwrap())
+}
+
+#[inline]
+fn read_u64(input: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(input[offset..offset + 8].try_into().unwrap())
+}
+
+/// RISC-V bytecode decode errors.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum RiscVDecodeError {
+    /// Bytecode does not start with [`RISCV_MAGIC`].
+    InvalidMagic,
+    /// Short input while processing the ELF header.
+    MissingInput,
+    /// Invalid ELF magic number.
+    InvalidElfMagic,
+    /// ELF is not 64-bit.
+    InvalidClass,
+    /// ELF is not little-endian.
+    InvalidEndianness,
+    /// Invalid ELF version.
+    InvalidVersion,
+    /// ELF is not an executable.
+    NotExecutable,
+    /// ELF is not built for RISC-V.
+    InvalidMachine,
+    /// Invalid program header entry size.
+    InvalidProgramHeaderSize,
+    /// Program header table is outside of the file.
+    ProgramHeadersOutOfBounds,
+    /// There is no loadable segment.
+    NoLoadableSegments,
+    /// Segment file size is bigger than its memory size.
+    InvalidSegmentSize,
+    /// Segment data or address range is out of bounds.
+    SegmentOutOfBounds,
+    /// Two loadable segments share memory.
+    OverlappingSegments,
+    /// Invalid section header entry size.
+    InvalidSectionHeaderSize,
+    /// Section header table is outside of the file.
+    SectionHeadersOutOfBounds,
+    /// Section data is outside of the file.
+    SectionOutOfBounds,
+    /// Entry point is not inside an executable segment.
+    InvalidEntryPoint,
+}
+
+impl fmt::Display for RiscVDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::InvalidMagic => "missing RISC-V magic byte",
+            Self::MissingInput => "short input for ELF header",
+            Self::InvalidElfMagic => "invalid ELF magic number",
+            Self::InvalidClass => "ELF is not 64-bit",
+            Self::InvalidEndianness => "ELF is not little-endian",
+            Self::InvalidVersion => "invalid ELF version",
+            Self::NotExecutable => "ELF is not an executable",
+            Self::InvalidMachine => "ELF is not built for RISC-V",
+            Self::InvalidProgramHeaderSize => "invalid program header size",
+            Self::ProgramHeadersOutOfBounds => "program headers out of bounds",
+            Self::NoLoadableSegments => "no loadable segments",
+            Self::InvalidSegmentSize => "segment file size exceeds memory size",
+            Self::SegmentOutOfBounds => "segment out of bounds",
+            Self::OverlappingSegments => "overlapping segments",
+            Self::InvalidSectionHeaderSize => "invalid section header size",
+            Self::SectionHeadersOutOfBounds => "section headers out of bounds",
+            Self::SectionOutOfBounds => "section out of bounds",
+            Self::InvalidEntryPoint => "entry point is not in an executable segment",
+        };
+        f.write_str(s)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RiscVDecodeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::vec;
+
+    /// Builds a minimal ELF with one executable segment holding `code` at `0x80300000`.
+    fn elf(code: &[u8]) -> Vec<u8> {
+        elf_segments(&[(0x8030_0000, code, code.len() as u64)])
+    }
+
+    /// Builds a minimal ELF with executable segments of `(vaddr, data, mem_size)`, the
+    /// entry point being the first one.
+    fn elf_segments(segments: &[(u64, &[u8], u64)]) -> Vec<u8> {
+        let mut offset = (EHDR_SIZE + PHDR_SIZE * segments.len()) as u64;
+        let mut elf = vec![RISCV_MAGIC];
+        elf.extend_from_slice(&ELF_MAGIC);
+        elf.extend_from_slice(&[ELFCLASS64, ELFDATA2LSB, EV_CURRENT]);
+        elf.resize(1 + 16, 0);
+        elf.extend_from_slice(&ET_EXEC.to_le_bytes());
+        elf.extend_from_slice(&EM_RISCV.to_le_bytes());
+        elf.extend_from_slice(&1u32.to_le_bytes());
+        elf.extend_from_slice(&segments[0].0.to_le_bytes());
+        elf.extend_from_slice(&(EHDR_SIZE as u64).to_le_bytes());
+        elf.extend_from_slice(&0u64.to_le_bytes());
+        elf.extend_from_slice(&0u32.to_le_bytes());
+        for half in [EHDR_SIZE, PHDR_SIZE, segments.len(), SHDR_SIZE, 0, 0] {
+            elf.extend_from_slice(&(half as u16).to_le_bytes());
+        }
+        for &(vaddr, data, mem_size) in segments {
+            elf.extend_from_slice(&PT_LOAD.to_le_bytes());
+            elf.extend_from_slice(&(PF_X | 4).to_le_bytes());
+            let size = data.len() as u64;
+            for word in [offset, vaddr, vaddr, size, mem_size, 0x1000] {
+                elf.extend_from_slice(&word.to_le_bytes());
+            }
+            offset += size;
+        }
+        for (_, data, _) in segments {
+            elf.extend_from_slice(data);
+        }
+        elf
+    }
+
+    #[test]
+    fn decode_riscv() {
+        // li t0, 0; ecall
+        let code = [0x93, 0x02, 0x00, 0x00, 0x73, 0x00, 0x00, 0x00];
+        let bytecode = RiscVBytecode::decode(elf(&code).into()).unwrap();
+        assert_eq!(bytecode.entry(), 0x8030_0000);
+        assert_eq!(bytecode.segments().len(), 1);
+        assert_eq!(bytecode.segment_data(&bytecode.segments()[0]), code);
+        assert_eq!(bytecode.raw()[0], RISCV_MAGIC);
+    }
+
+    #[test]
+    fn decode_errors() {
+        let valid = elf(&[0x73, 0x00, 0x00, 0x00]);
+        assert_eq!(
+            RiscVBytecode::decode(Bytes::new()),
+            Err(RiscVDecodeError::InvalidMagic)
+        );
+        assert_eq!(
+            RiscVBytecode::decode(valid[1..].to_vec().into()),
+            Err(RiscVDecodeError::InvalidMagic)
+        );
+        assert_eq!(
+            RiscVBytecode::decode(valid[..32].to_vec().into()),
+            Err(RiscVDecodeError::MissingInput)
+        );
+
+        let mut wrong_machine = valid.clone();
+        wrong_machine[1 + 18] = 0x3E;
+        assert_eq!(
+            RiscVBytecode::decode(wrong_machine.into()),
+            Err(RiscVDecodeError::InvalidMachine)
+        );
+
+        let mut entry_outside = valid.clone();
+        entry_outside[1 + 24..1 + 32].copy_from_slice(&0x8040_0000u64.to_le_bytes());
+        assert_eq!(
+            RiscVBytecode::decode(entry_outside.into()),
+            Err(RiscVDecodeError::InvalidEntryPoint)
+        );
+
+        let truncated = valid[..valid.len() - 1].to_vec();
+        assert_eq!(
+            RiscVBytecode::decode(truncated.into()),
+            Err(RiscVDecodeError::SegmentOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn memory_image() {
+        let bytecode = RiscVBytecode::decode(
+            elf_segments(&[
+                (0x8030_0000, &[0x73, 0x00, 0x00, 0x00], 4),
+                (0x8030_0008, &[1, 2], 0x100),
+            ])
+            .into(),
+        )
+        .unwrap();
+        assert_eq!(bytecode.memory_range(), 0x8030_0000..0x8030_0108);
+
+        assert_eq!(bytecode.image(9), None);
+        let image = bytecode.image(10).unwrap();
+        assert_eq!(image.start, 0x8030_0000);
+        assert_eq!(image.data, [0x73, 0x00, 0x00, 0x00, 0, 0, 0, 0, 1, 2]);
+
+        // clones share the image.
+        let clone = bytecode.clone();
+        assert!(std::ptr::eq(clone.image(10).unwrap(), image));
+        assert_eq!(clone, bytecode);
+    }
+
+    #[test]
+    fn resolve_riscv() {
+        use crate::Bytecode;
+
+        let raw = Bytes::from(elf(&[0x73, 0x00, 0x00, 0x00]));
+        let riscv = Bytecode::new_riscv(raw.clone()).unwrap();
+        let legacy = Bytecode::new_raw(raw.clone());
+        assert_eq!(legacy.clone().resolve_riscv(true), riscv);
+        assert_eq!(riscv.clone().resolve_riscv(true), riscv);
+        assert_eq!(riscv.resolve_riscv(false), legacy);
+        assert_eq!(legacy.clone().resolve_riscv(false), legacy);
+
+        // code starting with the marker that is not an ELF is the SELFDESTRUCT opcode.
+        let selfdestruct = Bytecode::new_raw(Bytes::from_static(&[RISCV_MAGIC]));
+        assert_eq!(selfdestruct.clone().resolve_riscv(true), selfdestruct);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_decodes() {
+        use serde::{de::value::StrDeserializer, Deserialize};
+
+        let deserialize = |raw: &[u8]| {
+            let hex = crate::hex::encode_prefixed(raw);
+            RiscVBytecode::deserialize(StrDeserializer::<serde::de::value::Error>::new(&hex))
+        };
+
+        let valid = elf(&[0x73, 0x00, 0x00, 0x00]);
+        let bytecode = deserialize(&valid).unwrap();
+        assert_eq!(
+            bytecode,
+            RiscVBytecode::decode(valid.clone().into()).unwrap()
+        );
+        assert_eq!(Bytes::from(bytecode), valid);
+
+        // segments pointing outside of the file are rejected instead of read.
+        assert!(deserialize(&valid[..valid.len() - 1]).is_err());
+    }
+}
@@ -31,6 +31,11 @@ pub const BLOCKHASH_STORAGE_ADDRESS: Address = address!("25a219378dad9b3503c8268
 /// Limit of maximum initcode size is `2 * MAX_CODE_SIZE`.
 pub const MAX_INITCODE_SIZE: usize = 2 * MAX_CODE_SIZE;
 
+/// Default maximum size of the DRAM of a RISC-V contract.
+///
+/// By default the limit is `0x800000` (8 MiB)
+pub const RISCV_DRAM_SIZE: u64 = 0x80_0000;
+
 /// The address of precompile 3, which is handled specially in a few places.
 pub const PRECOMPILE3: Address =
     Address::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]);
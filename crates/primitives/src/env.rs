@@ -304,6 +304,15 @@ pub struct CfgEnv {
     /// By default, it is set to `false`.
     #[cfg(feature = "optional_beneficiary_reward")]
     pub disable_beneficiary_reward: bool,
+    /// Executes code starting with the RISC-V marker as RISC-V contracts. When disabled, such
+    /// code is legacy bytecode like on mainnet, where the marker is the SELFDESTRUCT opcode.
+    /// By default, it is set to `false`.
+    #[cfg(feature = "riscv")]
+    pub enable_riscv: bool,
+    /// Maximum size in bytes of the DRAM of a RISC-V contract, accessing memory past it halts
+    /// the contract. By default it is [`RISCV_DRAM_SIZE`](crate::RISCV_DRAM_SIZE) (8 MiB).
+    #[cfg(feature = "riscv")]
+    pub riscv_dram_size: u64,
 }
 
 impl CfgEnv {
@@ -371,6 +380,16 @@ impl CfgEnv {
     pub fn is_beneficiary_reward_disabled(&self) -> bool {
         false
     }
+
+    #[cfg(feature = "riscv")]
+    pub fn is_riscv_enabled(&self) -> bool {
+        self.enable_riscv
+    }
+
+    #[cfg(not(feature = "riscv"))]
+    pub fn is_riscv_enabled(&self) -> bool {
+        false
+    }
 }
 
 impl Default for CfgEnv {
@@ -395,6 +414,10 @@ impl Default for CfgEnv {
             disable_base_fee: false,
             #[cfg(feature = "optional_beneficiary_reward")]
             disable_beneficiary_reward: false,
+            #[cfg(feature = "riscv")]
+            enable_riscv: false,
+            #[cfg(feature = "riscv")]
+            riscv_dram_size: crate::RISCV_DRAM_SIZE,
         }
     }
 }
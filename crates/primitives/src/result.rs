@@ -412,6 +412,18 @@ pub enum HaltReason {
     CreateContractStartingWithEF,
     /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
     CreateInitCodeSizeLimit,
+    /// Created contract starts with the RISC-V marker but is not a valid RISC-V ELF.
+    CreateContractInvalidRiscV,
+    /// RISC-V contract executed an illegal instruction.
+    RiscVInvalidInstruction,
+    /// RISC-V contract accessed memory or jumped to an address that is not aligned.
+    RiscVMisalignedAccess,
+    /// RISC-V contract accessed memory outside of its DRAM.
+    RiscVOutOfBounds,
+    /// RISC-V contract made a syscall with an unknown number.
+    RiscVUnknownSyscall,
+    /// RISC-V contract ran without a spec to price its syscalls.
+    RiscVSpecNotSet,
 
     /* Internal Halts that can be only found inside Inspector */
     OverflowPayment,
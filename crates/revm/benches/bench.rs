@@ -93,6 +93,8 @@ fn bench_transact<EXT>(g: &mut BenchmarkGroup<'_, WallTime>, evm: &mut Evm<'_, E
         Bytecode::LegacyRaw(_) => "raw",
         Bytecode::LegacyAnalyzed(_) => "analysed",
         Bytecode::Eof(_) => "eof",
+        #[cfg(feature = "riscv")]
+        Bytecode::RiscV(_) => "riscv",
     };
     let id = format!("transact/{state}");
     g.bench_function(id, |b| b.iter(|| evm.transact().unwrap()));
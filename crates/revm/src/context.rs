@@ -13,7 +13,10 @@ use revm_interpreter::as_usize_saturated;
 use crate::{
     db::{Database, EmptyDB},
     interpreter::{Host, LoadAccountResult, SStoreResult, SelfDestructResult},
-    primitives::{Address, Bytecode, Env, HandlerCfg, Log, B256, BLOCK_HASH_HISTORY, U256},
+    primitives::{
+        Address, Bytecode, Bytes, Env, HandlerCfg, Log, PrecompileResult, B256, BLOCK_HASH_HISTORY,
+        U256,
+    },
 };
 use std::boxed::Box;
 
@@ -192,4 +195,15 @@ impl<EXT, DB: Database> Host for Context<EXT, DB> {
             .map_err(|e| self.evm.error = Err(e))
             .ok()
     }
+
+    fn precompile(
+        &mut self,
+        address: Address,
+        input: &Bytes,
+        gas_limit: u64,
+    ) -> Option<PrecompileResult> {
+        self.evm
+            .precompiles
+            .call(address, input, gas_limit, &mut self.evm.inner)
+    }
 }
@@ -168,6 +168,8 @@ impl<DB: Database> EvmContext<DB> {
             .load_code(inputs.bytecode_address, &mut self.inner.db)?;
         let code_hash = account.info.code_hash();
         let bytecode = account.info.code.clone().unwrap_or_default();
+        #[cfg(feature = "riscv")]
+        let bytecode = self.inner.resolve_riscv(bytecode, code_hash);
 
         // Create subroutine checkpoint
         let checkpoint = self.journaled_state.checkpoint();
@@ -207,11 +209,13 @@ impl<DB: Database> EvmContext<DB> {
         } else if !bytecode.is_empty() {
             let contract =
                 Contract::new_with_context(inputs.input.clone(), bytecode, Some(code_hash), inputs);
+            let mut interpreter = Interpreter::new(contract, gas.limit(), inputs.is_static);
+            interpreter.set_spec_id(self.spec_id());
             // Create interpreter and executes call and push new CallStackFrame.
             Ok(FrameOrResult::new_call_frame(
                 inputs.return_memory_offset.clone(),
                 checkpoint,
-                Interpreter::new(contract, gas.limit(), inputs.is_static),
+                interpreter,
             ))
         } else {
             self.journaled_state.checkpoint_commit();
@@ -282,6 +286,8 @@ pub(crate) mod test_utils {
                 error: Ok(()),
                 #[cfg(feature = "optimism")]
                 l1_block_info: None,
+                #[cfg(feature = "riscv")]
+                riscv_code: Default::default(),
             },
             precompiles: ContextPrecompiles::default(),
         }
@@ -297,6 +303,8 @@ pub(crate) mod test_utils {
                 error: Ok(()),
                 #[cfg(feature = "optimism")]
                 l1_block_info: None,
+                #[cfg(feature = "riscv")]
+                riscv_code: Default::default(),
             },
             precompiles: ContextPrecompiles::default(),
         }
@@ -311,6 +319,15 @@ mod tests {
         primitives::{address, Bytecode},
         Frame, JournalEntry,
     };
+    #[cfg(feature = "riscv")]
+    use crate::{
+        interpreter::{gas, CreateInputs},
+        primitives::{
+            keccak256, AccountInfo, CancunSpec, CreateScheme, ExecutionResult, HaltReason, Output,
+            SpecId, TransactTo, B256, RISCV_MAGIC,
+        },
+        Evm,
+    };
     use std::boxed::Box;
     use test_utils::*;
 
@@ -397,4 +414,242 @@ mod tests {
         };
         assert_eq!(call_frame.return_memory_range, 0..0,);
     }
+
+    /// Reads a RISC-V test contract, prefixed with the RISC-V marker.
+    #[cfg(feature = "riscv")]
+    fn riscv_code(name: &str) -> Bytes {
+        let mut code = vec![RISCV_MAGIC];
+        code.extend(std::fs::read(format!("../../elf_test/{name}")).unwrap());
+        code.into()
+    }
+
+    #[test]
+    #[cfg(feature = "riscv")]
+    fn test_make_create_frame_riscv() {
+        let mut env = Env::default();
+        env.cfg.enable_riscv = true;
+        let cdb = CacheDB::new(EmptyDB::default());
+        let bal = U256::from(3_000_000_000_u128);
+        let mut context = create_cache_db_evm_context_with_balance(Box::new(env), cdb, bal);
+        let create_inputs = CreateInputs {
+            caller: MOCK_CALLER,
+            scheme: CreateScheme::Create,
+            value: U256::ZERO,
+            init_code: riscv_code("create_example"),
+            gas_limit: 1_000_000,
+        };
+        let res = context.make_create_frame(SpecId::CANCUN, &create_inputs);
+        let Ok(FrameOrResult::Frame(Frame::Create(create_frame))) = res else {
+            panic!("Expected FrameOrResult::Frame(Frame::Create(..))");
+        };
+        let interpreter = &create_frame.frame_data.interpreter;
+        assert!(interpreter.contract.bytecode.is_riscv());
+        assert!(interpreter.riscv_emulator.is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "riscv")]
+    fn test_create_return_invalid_riscv() {
+        let mut env = Env::default();
+        env.cfg.enable_riscv = true;
+        let db = EmptyDB::default();
+        let mut context = test_utils::create_empty_evm_context(Box::new(env), db);
+        let address = address!("dead10000000000000000000000000000001dead");
+        let checkpoint = context.journaled_state.checkpoint();
+        let mut result = InterpreterResult {
+            result: InstructionResult::Return,
+            output: Bytes::from_static(&[RISCV_MAGIC, 0x7F, b'E', b'L', b'F']),
+            gas: Gas::new(1_000_000),
+        };
+        context.create_return::<CancunSpec>(&mut result, address, checkpoint);
+        assert_eq!(result.result, InstructionResult::CreateContractInvalidRiscV);
+    }
+
+    #[test]
+    #[cfg(feature = "riscv")]
+    fn test_riscv_create_and_call() {
+        let mut evm = Evm::builder()
+            .with_db(CacheDB::new(EmptyDB::default()))
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::Create;
+                tx.data = riscv_code("create_example");
+                tx.gas_limit = 2_000_000;
+            })
+            .build();
+
+        let ExecutionResult::Success {
+            output: Output::Create(_, Some(address)),
+            gas_used,
+            ..
+        } = evm.transact_commit().unwrap()
+        else {
+            panic!("Expected successful create");
+        };
+        let runtime = riscv_code("sstore_and_sload_example");
+        assert!(gas_used > runtime.len() as u64 * gas::CODEDEPOSIT);
+
+        let code = evm.db().accounts[&address].info.code.clone().unwrap();
+        assert!(code.is_riscv());
+        assert_eq!(code.original_bytes(), runtime);
+        assert_eq!(code.hash_slow(), keccak256(&runtime));
+
+        evm.tx_mut().transact_to = TransactTo::Call(address);
+        evm.tx_mut().data = Bytes::new();
+        let result = evm.transact_commit().unwrap();
+        assert!(result.is_success());
+        assert_eq!(
+            evm.db().accounts[&address].storage[&U256::from_limbs([42, 0, 0, 1])],
+            U256::from_limbs([0xdeadbeef, 0x11, 0x22, 0x33])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "riscv")]
+    fn test_riscv_create_syscalls() {
+        let mut evm = Evm::builder()
+            .with_db(CacheDB::new(EmptyDB::default()))
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::Create;
+                tx.data = riscv_code("deploy_example");
+                tx.gas_limit = 5_000_000;
+            })
+            .build();
+
+        let ExecutionResult::Success {
+            output: Output::Create(_, Some(address)),
+            ..
+        } = evm.transact_commit().unwrap()
+        else {
+            panic!("Expected successful create");
+        };
+
+        let init_code = riscv_code("create_example");
+        let created = [
+            address.create(1),
+            address.create2(B256::with_last_byte(1), keccak256(&init_code)),
+        ];
+        let runtime = riscv_code("sstore_and_sload_example");
+        for (slot, created) in created.into_iter().enumerate() {
+            let account = &evm.db().accounts[&address];
+            assert_eq!(
+                account.storage[&U256::from(slot)],
+                U256::from_be_bytes(created.into_word().0)
+            );
+            let code = evm.db().accounts[&created].info.code.clone().unwrap();
+            assert!(code.is_riscv());
+            assert_eq!(code.original_bytes(), runtime);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "riscv")]
+    fn test_riscv_stored_as_legacy() {
+        let code = riscv_code("sstore_and_sload_example");
+        let address = address!("dead10000000000000000000000000000001dead");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            address,
+            AccountInfo::new(
+                U256::ZERO,
+                1,
+                keccak256(&code),
+                Bytecode::new_raw(code.clone()),
+            ),
+        );
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::Call(address);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        // code stored as legacy bytecode runs as RISC-V code like decoded code.
+        let result = evm.transact_commit().unwrap();
+        assert!(result.is_success());
+        assert_eq!(
+            evm.db().accounts[&address].storage[&U256::from_limbs([42, 0, 0, 1])],
+            U256::from_limbs([0xdeadbeef, 0x11, 0x22, 0x33])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "riscv")]
+    fn test_riscv_code_cache() {
+        let code = riscv_code("sstore_and_sload_example");
+        let code_hash = keccak256(&code);
+        let mut env = Env::default();
+        env.cfg.enable_riscv = true;
+        let mut context = create_empty_evm_context(Box::new(env), EmptyDB::default());
+
+        // code is decoded once per code hash, and the calls share its memory image.
+        let first = context.resolve_riscv(Bytecode::new_raw(code.clone()), code_hash);
+        let second = context.resolve_riscv(Bytecode::new_raw(code.clone()), code_hash);
+        let image = |bytecode: &Bytecode| {
+            core::ptr::from_ref(bytecode.riscv().unwrap().image(u64::MAX).unwrap())
+        };
+        assert_eq!(image(&first), image(&second));
+        assert_eq!(context.riscv_code.len(), 1);
+
+        // EVM code is not cached.
+        let evm = Bytecode::new_raw(Bytes::from_static(&[0x00]));
+        assert_eq!(context.resolve_riscv(evm.clone(), evm.hash_slow()), evm);
+        assert_eq!(context.riscv_code.len(), 1);
+
+        // without RISC-V execution the code is legacy bytecode.
+        context.env.cfg.enable_riscv = false;
+        let legacy = context.resolve_riscv(first, code_hash);
+        assert_eq!(legacy, Bytecode::new_raw(code));
+    }
+
+    #[test]
+    #[cfg(feature = "riscv")]
+    fn test_riscv_disabled() {
+        let mut code = vec![RISCV_MAGIC];
+        code.extend(std::fs::read("../../elf_test/sstore_and_sload_example").unwrap());
+        let riscv = Bytecode::new_riscv(code.into()).unwrap();
+        let address = address!("dead10000000000000000000000000000001dead");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            address,
+            AccountInfo::new(U256::ZERO, 1, riscv.hash_slow(), riscv),
+        );
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::Call(address);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        // the marker is the SELFDESTRUCT opcode of legacy bytecode, which underflows the
+        // empty stack.
+        let result = evm.transact_commit().unwrap();
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::StackUnderflow,
+                ..
+            }
+        ));
+
+        // created code starting with the marker is legacy bytecode too.
+        // MSTORE8(0, 0xFF) RETURN(0, 1)
+        evm.tx_mut().transact_to = TransactTo::Create;
+        evm.tx_mut().data =
+            Bytes::from_static(&[0x60, 0xFF, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xF3]);
+        let ExecutionResult::Success {
+            output: Output::Create(_, Some(created)),
+            ..
+        } = evm.transact_commit().unwrap()
+        else {
+            panic!("Expected successful create");
+        };
+        let code = evm.db().accounts[&created].info.code.clone().unwrap();
+        assert!(!code.is_riscv());
+        assert_eq!(code.original_bytes(), [RISCV_MAGIC][..]);
+    }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "riscv")]
+use crate::primitives::{HashMap, RiscVBytecode, RISCV_MAGIC};
 use crate::{
     db::Database,
     interpreter::{
@@ -31,6 +33,10 @@ pub struct InnerEvmContext<DB: Database> {
     /// Used as temporary value holder to store L1 block info.
     #[cfg(feature = "optimism")]
     pub l1_block_info: Option<crate::optimism::L1BlockInfo>,
+    /// RISC-V code of the called contracts by code hash, see
+    /// [`InnerEvmContext::resolve_riscv`].
+    #[cfg(feature = "riscv")]
+    pub riscv_code: HashMap<B256, RiscVBytecode>,
 }
 
 impl<DB: Database + Clone> Clone for InnerEvmContext<DB>
@@ -45,6 +51,8 @@ where
             error: self.error.clone(),
             #[cfg(feature = "optimism")]
             l1_block_info: self.l1_block_info.clone(),
+            #[cfg(feature = "riscv")]
+            riscv_code: self.riscv_code.clone(),
         }
     }
 }
@@ -58,6 +66,8 @@ impl<DB: Database> InnerEvmContext<DB> {
             error: Ok(()),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
+            #[cfg(feature = "riscv")]
+            riscv_code: HashMap::new(),
         }
     }
 
@@ -71,6 +81,8 @@ impl<DB: Database> InnerEvmContext<DB> {
             error: Ok(()),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
+            #[cfg(feature = "riscv")]
+            riscv_code: HashMap::new(),
         }
     }
 
@@ -86,6 +98,8 @@ impl<DB: Database> InnerEvmContext<DB> {
             error: Ok(()),
             #[cfg(feature = "optimism")]
             l1_block_info: self.l1_block_info,
+            #[cfg(feature = "riscv")]
+            riscv_code: self.riscv_code,
         }
     }
 
@@ -95,6 +109,28 @@ impl<DB: Database> InnerEvmContext<DB> {
         self.journaled_state.spec
     }
 
+    /// Returns the code of an account with hash `code_hash` as it is executed, see
+    /// [`Bytecode::resolve_riscv`].
+    ///
+    /// RISC-V code is cached by code hash, so the ELF of a contract is decoded and its memory
+    /// image is built once, and all the frames calling it share them.
+    #[cfg(feature = "riscv")]
+    pub fn resolve_riscv(&mut self, bytecode: Bytecode, code_hash: B256) -> Bytecode {
+        if !self.env.cfg.is_riscv_enabled() {
+            return bytecode.resolve_riscv(false);
+        }
+        if let Some(riscv) = self.riscv_code.get(&code_hash) {
+            return Bytecode::RiscV(riscv.clone());
+        }
+        match bytecode.resolve_riscv(true) {
+            Bytecode::RiscV(riscv) => {
+                self.riscv_code.insert(code_hash, riscv.clone());
+                Bytecode::RiscV(riscv)
+            }
+            bytecode => bytecode,
+        }
+    }
+
     /// Load access list for berlin hard fork.
     ///
     /// Loading of accounts/storages is needed to make them warm.
@@ -402,6 +438,10 @@ impl<DB: Database> InnerEvmContext<DB> {
         };
 
         let bytecode = Bytecode::new_raw(inputs.init_code.clone());
+        // Init code that decodes as a RISC-V ELF is run as a RISC-V constructor if RISC-V
+        // execution is enabled.
+        #[cfg(feature = "riscv")]
+        let bytecode = bytecode.resolve_riscv(self.env.cfg.is_riscv_enabled());
 
         let contract = Contract::new(
             Bytes::new(),
@@ -412,10 +452,13 @@ impl<DB: Database> InnerEvmContext<DB> {
             inputs.value,
         );
 
+        let mut interpreter = Interpreter::new(contract, gas.limit(), false);
+        interpreter.set_spec_id(spec_id);
+
         Ok(FrameOrResult::new_create_frame(
             created_address,
             checkpoint,
-            Interpreter::new(contract, gas.limit(), false),
+            interpreter,
         ))
     }
 
@@ -488,14 +531,37 @@ impl<DB: Database> InnerEvmContext<DB> {
                 interpreter_result.output = Bytes::new();
             }
         }
+
+        // Code starting with the RISC-V marker needs to be a valid RISC-V ELF if RISC-V
+        // execution is enabled.
+        #[cfg(feature = "riscv")]
+        let riscv = if self.env.cfg.is_riscv_enabled()
+            && interpreter_result.output.first() == Some(&RISCV_MAGIC)
+        {
+            let Ok(riscv) = Bytecode::new_riscv(interpreter_result.output.clone()) else {
+                self.journaled_state.checkpoint_revert(journal_checkpoint);
+                interpreter_result.result = InstructionResult::CreateContractInvalidRiscV;
+                return;
+            };
+            Some(riscv)
+        } else {
+            None
+        };
+        #[cfg(not(feature = "riscv"))]
+        let riscv = None;
+
         // if we have enough gas we can commit changes.
         self.journaled_state.checkpoint_commit();
 
         // Do analysis of bytecode straight away.
-        let bytecode = match self.env.cfg.perf_analyse_created_bytecodes {
-            AnalysisKind::Raw => Bytecode::new_raw(interpreter_result.output.clone()),
-            AnalysisKind::Analyse => {
-                to_analysed(Bytecode::new_raw(interpreter_result.output.clone()))
+        let bytecode = if let Some(riscv) = riscv {
+            riscv
+        } else {
+            match self.env.cfg.perf_analyse_created_bytecodes {
+                AnalysisKind::Raw => Bytecode::new_raw(interpreter_result.output.clone()),
+                AnalysisKind::Analyse => {
+                    to_analysed(Bytecode::new_raw(interpreter_result.output.clone()))
+                }
             }
         };
 
@@ -374,3 +374,265 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
         post_exec.output(ctx, result)
     }
 }
+
+#[cfg(all(test, feature = "riscv"))]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{AccountState, Database, InMemoryDB},
+        interpreter::opcode::*,
+        primitives::{
+            address, b256, AccountInfo, Address, Bytecode, Bytes, Output, SpecId, SuccessReason,
+            B256, RISCV_MAGIC, U256,
+        },
+    };
+    use alloy_sol_types::{sol, SolCall};
+
+    sol! {
+        interface IStorage {
+            function set(uint256 value) external;
+            function get() external view returns (uint256);
+        }
+    }
+
+    /// Code of a contract that calls the address in the first 20 bytes of the calldata,
+    /// with the `CALL` or `DELEGATECALL` opcode, passing the rest of the calldata as input,
+    /// and returns or reverts with the output. Same as `elf_test/proxy_example`, without
+    /// the counter.
+    fn proxy_code(opcode: u8) -> Bytecode {
+        let mut code = vec![CALLDATASIZE, PUSH1, 0, PUSH1, 0, CALLDATACOPY];
+        code.extend([PUSH1, 0, PUSH1, 0, PUSH1, 20, CALLDATASIZE, SUB, PUSH1, 20]);
+        if opcode == CALL {
+            code.extend([PUSH1, 0]);
+        }
+        code.extend([PUSH1, 0, MLOAD, PUSH1, 96, SHR, GAS, opcode]);
+        code.extend([RETURNDATASIZE, PUSH1, 0, PUSH1, 0, RETURNDATACOPY]);
+        let ok = code.len() as u8 + 7;
+        code.extend([PUSH1, ok, JUMPI, RETURNDATASIZE, PUSH1, 0, REVERT]);
+        code.extend([JUMPDEST, RETURNDATASIZE, PUSH1, 0, RETURN]);
+        Bytecode::new_raw(code.into())
+    }
+
+    /// Code of the RISC-V contract `name` in `elf_test`.
+    fn riscv_code(name: &str) -> Bytecode {
+        let mut code = vec![RISCV_MAGIC];
+        code.extend(std::fs::read(format!("../../elf_test/{name}")).unwrap());
+        Bytecode::new_riscv(code.into()).unwrap()
+    }
+
+    /// Returns an EVM with the given contracts deployed.
+    fn evm_with(contracts: Vec<(Address, Bytecode)>) -> Evm<'static, (), InMemoryDB> {
+        Evm::builder()
+            .with_db(InMemoryDB::default())
+            .modify_db(|db| {
+                for (address, code) in contracts {
+                    let info = AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code);
+                    db.insert_account_info(address, info);
+                }
+            })
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .modify_tx_env(|tx| tx.gas_limit = 1_000_000)
+            .build()
+    }
+
+    /// Commits a transaction calling `to` with `input`.
+    fn transact(evm: &mut Evm<'_, (), InMemoryDB>, to: Address, input: Bytes) -> ExecutionResult {
+        evm.context.evm.env.tx.transact_to = TransactTo::Call(to);
+        evm.context.evm.env.tx.data = input;
+        evm.transact_commit().unwrap()
+    }
+
+    /// Returns the output of a successful transaction.
+    fn returned(result: ExecutionResult) -> Bytes {
+        match result {
+            ExecutionResult::Success {
+                reason: SuccessReason::Return,
+                output: Output::Call(output),
+                ..
+            } => output,
+            result => panic!("transaction failed: {result:?}"),
+        }
+    }
+
+    /// Concatenates the addresses of a chain of proxies with the input of the last callee.
+    fn through(proxies: &[Address], input: impl AsRef<[u8]>) -> Bytes {
+        let mut data: Vec<u8> = proxies.iter().flat_map(|proxy| proxy.0).collect();
+        data.extend_from_slice(input.as_ref());
+        data.into()
+    }
+
+    const STORAGE: Address = address!("2000000000000000000000000000000000000002");
+    const EVM_PROXY: Address = address!("3000000000000000000000000000000000000003");
+    const RISCV_PROXY: Address = address!("4000000000000000000000000000000000000004");
+    const DELEGATE_PROXY: Address = address!("5000000000000000000000000000000000000005");
+
+    #[test]
+    fn mixed_call_stack() {
+        let mut evm = evm_with(vec![
+            (STORAGE, riscv_code("storage_example")),
+            (EVM_PROXY, proxy_code(CALL)),
+            (RISCV_PROXY, riscv_code("proxy_example")),
+        ]);
+
+        // EVM -> RISC-V -> EVM -> RISC-V
+        let proxies = [RISCV_PROXY, EVM_PROXY, STORAGE];
+        let value = U256::from(7);
+        let input = through(&proxies, IStorage::setCall { value }.abi_encode());
+        assert!(returned(transact(&mut evm, EVM_PROXY, input)).is_empty());
+
+        let input = through(&proxies, IStorage::getCall {}.abi_encode());
+        let output = returned(transact(&mut evm, EVM_PROXY, input));
+        let returns = IStorage::getCall::abi_decode_returns(&output, true).unwrap();
+        assert_eq!(returns._0, value);
+
+        // reverts and their output travel back up the stack.
+        let input = through(&proxies, [0xde, 0xad, 0xbe, 0xef]);
+        let result = transact(&mut evm, EVM_PROXY, input);
+        assert!(matches!(result, ExecutionResult::Revert { output, .. } if output.is_empty()));
+
+        let db = evm.db_mut();
+        assert_eq!(db.storage(STORAGE, U256::ZERO), Ok(value));
+        assert_eq!(db.storage(RISCV_PROXY, U256::ZERO), Ok(U256::from(2)));
+    }
+
+    #[test]
+    fn riscv_reentrancy() {
+        let mut evm = evm_with(vec![
+            (STORAGE, riscv_code("storage_example")),
+            (EVM_PROXY, proxy_code(CALL)),
+            (RISCV_PROXY, riscv_code("proxy_example")),
+        ]);
+
+        // RISC-V -> EVM -> RISC-V -> EVM -> RISC-V, entering the RISC-V proxy twice.
+        let proxies = [EVM_PROXY, RISCV_PROXY, EVM_PROXY, STORAGE];
+        let value = U256::MAX;
+        let input = through(&proxies, IStorage::setCall { value }.abi_encode());
+        assert!(returned(transact(&mut evm, RISCV_PROXY, input)).is_empty());
+
+        let input = through(&proxies, IStorage::getCall {}.abi_encode());
+        let output = returned(transact(&mut evm, RISCV_PROXY, input));
+        let returns = IStorage::getCall::abi_decode_returns(&output, true).unwrap();
+        assert_eq!(returns._0, value);
+
+        let db = evm.db_mut();
+        assert_eq!(db.storage(STORAGE, U256::ZERO), Ok(value));
+        assert_eq!(db.storage(RISCV_PROXY, U256::ZERO), Ok(U256::from(4)));
+    }
+
+    #[test]
+    fn delegatecall_into_riscv() {
+        let mut evm = evm_with(vec![
+            (STORAGE, riscv_code("storage_example")),
+            (DELEGATE_PROXY, proxy_code(DELEGATECALL)),
+        ]);
+
+        // the RISC-V code runs on the storage of the proxy.
+        let value = U256::from(7);
+        let input = through(&[STORAGE], IStorage::setCall { value }.abi_encode());
+        assert!(returned(transact(&mut evm, DELEGATE_PROXY, input)).is_empty());
+
+        let input = through(&[STORAGE], IStorage::getCall {}.abi_encode());
+        let output = returned(transact(&mut evm, DELEGATE_PROXY, input));
+        let returns = IStorage::getCall::abi_decode_returns(&output, true).unwrap();
+        assert_eq!(returns._0, value);
+
+        let db = evm.db_mut();
+        assert_eq!(db.storage(DELEGATE_PROXY, U256::ZERO), Ok(value));
+        assert_eq!(db.storage(STORAGE, U256::ZERO), Ok(U256::ZERO));
+    }
+
+    #[test]
+    fn riscv_call_refunds() {
+        let mut evm = evm_with(vec![
+            (STORAGE, riscv_code("storage_example")),
+            (EVM_PROXY, proxy_code(CALL)),
+            (RISCV_PROXY, riscv_code("proxy_example")),
+        ]);
+        let value = U256::from(7);
+        let input = through(&[STORAGE], IStorage::setCall { value }.abi_encode());
+        returned(transact(&mut evm, EVM_PROXY, input));
+
+        // clearing the slot is refunded through the RISC-V and EVM frames above it.
+        let input = through(
+            &[RISCV_PROXY, STORAGE],
+            IStorage::setCall { value: U256::ZERO }.abi_encode(),
+        );
+        let ExecutionResult::Success { gas_refunded, .. } = transact(&mut evm, EVM_PROXY, input)
+        else {
+            panic!("set failed");
+        };
+        assert_eq!(gas_refunded, 4800);
+    }
+
+    #[test]
+    fn riscv_precompiles() {
+        let mut evm = evm_with(vec![(STORAGE, riscv_code("precompile_example"))]);
+
+        // sha256
+        let input = through(&[Address::with_last_byte(2)], b"abc");
+        let output = returned(transact(&mut evm, STORAGE, input));
+        assert_eq!(
+            output[..],
+            b256!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")[..]
+        );
+
+        // identity
+        let word = B256::repeat_byte(0x42);
+        let input = through(&[Address::with_last_byte(4)], word);
+        let ExecutionResult::Success {
+            gas_used, output, ..
+        } = transact(&mut evm, STORAGE, input)
+        else {
+            panic!("identity failed");
+        };
+        assert_eq!(output.into_data()[..], word[..]);
+        // no call frame is made for the precompile.
+        assert!(gas_used < 30_000);
+
+        // ecrecover with invalid input has no output, which is not an error.
+        let input = through(&[Address::with_last_byte(1)], [0; 128]);
+        assert_eq!(returned(transact(&mut evm, STORAGE, input))[..], [0; 32]);
+    }
+
+    #[test]
+    fn riscv_selfdestruct() {
+        const BENEFICIARY: Address = address!("6000000000000000000000000000000000000006");
+        let input = Bytes::from(BENEFICIARY.into_word().0);
+        for (spec_id, destroyed) in [(SpecId::SHANGHAI, true), (SpecId::CANCUN, false)] {
+            let mut evm = evm_with(vec![(STORAGE, riscv_code("selfdestruct_example"))]);
+            evm.modify_spec_id(spec_id);
+            evm.context
+                .evm
+                .db
+                .accounts
+                .get_mut(&STORAGE)
+                .unwrap()
+                .info
+                .balance = U256::from(100);
+
+            let result = transact(&mut evm, STORAGE, input.clone());
+            assert!(
+                matches!(
+                    result,
+                    ExecutionResult::Success {
+                        reason: SuccessReason::SelfDestruct,
+                        ..
+                    }
+                ),
+                "{spec_id:?}"
+            );
+
+            // EIP-6780: since Cancun, only contracts created in the same transaction are
+            // destroyed, others only send their balance.
+            let db = &evm.context.evm.db;
+            assert_eq!(db.accounts[&BENEFICIARY].info.balance, U256::from(100));
+            let contract = &db.accounts[&STORAGE];
+            assert_eq!(contract.info.balance, U256::ZERO);
+            assert_eq!(
+                contract.account_state == AccountState::NotExisting,
+                destroyed,
+                "{spec_id:?}"
+            );
+        }
+    }
+}
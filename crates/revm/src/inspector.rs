@@ -1,3 +1,5 @@
+#[cfg(feature = "riscv")]
+use crate::interpreter::{RVEmu, Syscall};
 use crate::{
     interpreter::{CallInputs, CreateInputs, EOFCreateInput, EOFCreateOutcome, Interpreter},
     primitives::{db::Database, Address, Log, U256},
@@ -65,6 +67,65 @@ pub trait Inspector<DB: Database> {
         let _ = context;
     }
 
+    /// Called after a RISC-V contract has executed an instruction other than `ecall`,
+    /// with the address `pc` and the encoding `inst` of the instruction.
+    ///
+    /// RISC-V contracts do not call `step` and `step_end`, the program counter and
+    /// registers of the emulator are available on `riscv`.
+    #[cfg(feature = "riscv")]
+    #[inline]
+    fn riscv_step(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        pc: u64,
+        inst: u64,
+    ) {
+        let _ = interp;
+        let _ = riscv;
+        let _ = context;
+        let _ = pc;
+        let _ = inst;
+    }
+
+    /// Called before a RISC-V contract executes a syscall, with the syscall arguments
+    /// in the registers of `riscv`.
+    #[cfg(feature = "riscv")]
+    #[inline]
+    fn riscv_syscall(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        syscall: Syscall,
+    ) {
+        let _ = interp;
+        let _ = riscv;
+        let _ = context;
+        let _ = syscall;
+    }
+
+    /// Called after `riscv_syscall` when the syscall has been executed, with its results
+    /// in the registers of `riscv`.
+    ///
+    /// Setting `interp.instruction_result` to anything other than [crate::interpreter::InstructionResult::Continue] alters the execution
+    /// of the interpreter.
+    #[cfg(feature = "riscv")]
+    #[inline]
+    fn riscv_syscall_end(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        syscall: Syscall,
+    ) {
+        let _ = interp;
+        let _ = riscv;
+        let _ = context;
+        let _ = syscall;
+    }
+
     /// Called when a log is emitted.
     #[inline]
     fn log(&mut self, context: &mut EvmContext<DB>, log: &Log) {
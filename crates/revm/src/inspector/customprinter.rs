@@ -5,6 +5,8 @@ use revm_interpreter::CallOutcome;
 use revm_interpreter::CreateOutcome;
 use revm_interpreter::OpCode;
 
+#[cfg(feature = "riscv")]
+use crate::interpreter::{RVEmu, Syscall};
 use crate::{
     inspectors::GasInspector,
     interpreter::{CallInputs, CreateInputs, Interpreter},
@@ -56,6 +58,65 @@ impl<DB: Database> Inspector<DB> for CustomPrintTracer {
         self.gas_inspector.step_end(interp, context);
     }
 
+    #[cfg(feature = "riscv")]
+    fn riscv_step(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        pc: u64,
+        inst: u64,
+    ) {
+        let gas_remaining = interp.gas.remaining();
+
+        println!(
+            "depth:{}, PC:{:#x}, gas:{:#x}({}), INST: {:#010x}  refund:{:#x}({}) Registers:{:?}",
+            context.journaled_state.depth(),
+            pc,
+            gas_remaining,
+            gas_remaining,
+            inst,
+            interp.gas.refunded(),
+            interp.gas.refunded(),
+            (0..32).map(|i| riscv.register(i)).collect::<Vec<_>>(),
+        );
+
+        self.gas_inspector
+            .riscv_step(interp, riscv, context, pc, inst);
+    }
+
+    #[cfg(feature = "riscv")]
+    fn riscv_syscall(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        syscall: Syscall,
+    ) {
+        println!(
+            "depth:{}, PC:{:#x}, SYSCALL: {:?}({:#x})",
+            context.journaled_state.depth(),
+            riscv.pc(),
+            syscall,
+            syscall as u64,
+        );
+
+        self.gas_inspector
+            .riscv_syscall(interp, riscv, context, syscall);
+    }
+
+    #[cfg(feature = "riscv")]
+    fn riscv_syscall_end(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        syscall: Syscall,
+    ) {
+        self.gas_inspector
+            .riscv_syscall_end(interp, riscv, context, syscall);
+    }
+
     fn call_end(
         &mut self,
         context: &mut EvmContext<DB>,
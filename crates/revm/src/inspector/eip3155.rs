@@ -7,10 +7,16 @@ use crate::{
     EvmContext, Inspector,
 };
 use revm_interpreter::OpCode;
+#[cfg(feature = "riscv")]
+use revm_interpreter::{RVEmu, Syscall};
 use serde::Serialize;
 use std::io::Write;
 
 /// [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) tracer [Inspector].
+///
+/// RISC-V contracts are traced with a line for every instruction, with the major opcode of
+/// the instruction as `op`, and a line for every syscall, with the syscall number as `op`.
+/// Their stack and memory are empty.
 pub struct TracerEip3155 {
     output: Box<dyn Write>,
     gas_inspector: GasInspector,
@@ -162,6 +168,41 @@ impl TracerEip3155 {
         self.output.flush()
     }
 
+    /// Writes the trace line of a RISC-V instruction or syscall at `pc` that started with
+    /// `gas` remaining, its cost being the last one seen by the gas inspector.
+    #[cfg(feature = "riscv")]
+    fn write_riscv<DB: Database>(
+        &mut self,
+        interp: &Interpreter,
+        context: &EvmContext<DB>,
+        pc: u64,
+        op: u8,
+        gas: u64,
+    ) {
+        let value = Output {
+            pc,
+            op,
+            gas: hex_number(gas),
+            gas_cost: hex_number(self.gas_inspector.last_gas_cost()),
+            stack: Vec::new(),
+            depth: context.journaled_state.depth(),
+            return_data: "0x".to_string(),
+            refund: hex_number(interp.gas.refunded() as u64),
+            mem_size: "0".to_string(),
+
+            op_name: None,
+            error: if !interp.instruction_result.is_ok() {
+                Some(format!("{:?}", interp.instruction_result))
+            } else {
+                None
+            },
+            memory: None,
+            storage: None,
+            return_stack: None,
+        };
+        let _ = self.write_value(&value);
+    }
+
     fn print_summary<DB: Database>(
         &mut self,
         result: &InterpreterResult,
@@ -235,6 +276,48 @@ impl<DB: Database> Inspector<DB> for TracerEip3155 {
         let _ = self.write_value(&value);
     }
 
+    #[cfg(feature = "riscv")]
+    fn riscv_step(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        pc: u64,
+        inst: u64,
+    ) {
+        let gas = self.gas_inspector.gas_remaining();
+        self.gas_inspector
+            .riscv_step(interp, riscv, context, pc, inst);
+        self.write_riscv(interp, context, pc, (inst & 0x7f) as u8, gas);
+    }
+
+    #[cfg(feature = "riscv")]
+    fn riscv_syscall(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        syscall: Syscall,
+    ) {
+        self.gas_inspector
+            .riscv_syscall(interp, riscv, context, syscall);
+        self.pc = riscv.pc() as usize;
+        self.gas = self.gas_inspector.gas_remaining();
+    }
+
+    #[cfg(feature = "riscv")]
+    fn riscv_syscall_end(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        context: &mut EvmContext<DB>,
+        syscall: Syscall,
+    ) {
+        self.gas_inspector
+            .riscv_syscall_end(interp, riscv, context, syscall);
+        self.write_riscv(interp, context, self.pc as u64, syscall as u8, self.gas);
+    }
+
     fn call_end(
         &mut self,
         context: &mut EvmContext<DB>,
@@ -284,3 +367,80 @@ fn hex_number_u256(b: &U256) -> String {
         format!("0x{s}")
     }
 }
+
+#[cfg(all(test, feature = "riscv"))]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector_handle_register,
+        primitives::{Address, Bytecode, TransactTo, RISCV_MAGIC},
+        Evm,
+    };
+    use std::sync::{Arc, Mutex};
+
+    /// Writer that keeps the trace for the test to read.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn trace_riscv() {
+        let mut code = vec![RISCV_MAGIC];
+        code.extend(std::fs::read("../../elf_test/log_example").unwrap());
+        let bytecode = Bytecode::new_riscv(code.into()).unwrap();
+        let entry = bytecode.riscv().unwrap().entry();
+
+        let buffer = Buffer::default();
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(TracerEip3155::new(Box::new(buffer.clone())))
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .modify_tx_env(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let trace = buffer.0.lock().unwrap();
+        let lines: Vec<serde_json::Value> = trace
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        let (summary, steps) = lines.split_last().unwrap();
+        assert_eq!(summary["pass"], true);
+
+        assert_eq!(steps[0]["pc"], entry);
+        assert!(steps.iter().all(|step| step["depth"] == 1));
+        // every line starts with the gas left after the line before it.
+        for pair in steps.windows(2) {
+            let gas = |step: &serde_json::Value, field: &str| {
+                u64::from_str_radix(step[field].as_str().unwrap().trim_start_matches("0x"), 16)
+                    .unwrap()
+            };
+            assert_eq!(
+                gas(&pair[0], "gas") - gas(&pair[0], "gasCost"),
+                gas(&pair[1], "gas")
+            );
+        }
+        // instructions have 7-bit opcodes, LOG2 can only be the syscall.
+        let logs = steps
+            .iter()
+            .filter(|step| step["op"] == Syscall::Log2 as u8);
+        assert_eq!(logs.count(), 1);
+        assert_eq!(steps.last().unwrap()["op"], Syscall::Return as u8);
+    }
+}
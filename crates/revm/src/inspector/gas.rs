@@ -53,6 +53,33 @@ impl<DB: Database> Inspector<DB> for GasInspector {
         self.gas_remaining = remaining;
     }
 
+    #[cfg(feature = "riscv")]
+    fn riscv_step(
+        &mut self,
+        interp: &mut crate::interpreter::Interpreter,
+        _riscv: &crate::interpreter::RVEmu,
+        _context: &mut EvmContext<DB>,
+        _pc: u64,
+        _inst: u64,
+    ) {
+        let remaining = interp.gas.remaining();
+        self.last_gas_cost = self.gas_remaining.saturating_sub(remaining);
+        self.gas_remaining = remaining;
+    }
+
+    #[cfg(feature = "riscv")]
+    fn riscv_syscall_end(
+        &mut self,
+        interp: &mut crate::interpreter::Interpreter,
+        _riscv: &crate::interpreter::RVEmu,
+        _context: &mut EvmContext<DB>,
+        _syscall: crate::interpreter::Syscall,
+    ) {
+        let remaining = interp.gas.remaining();
+        self.last_gas_cost = self.gas_remaining.saturating_sub(remaining);
+        self.gas_remaining = remaining;
+    }
+
     fn call_end(
         &mut self,
         _context: &mut EvmContext<DB>,
@@ -1,3 +1,5 @@
+#[cfg(feature = "riscv")]
+use crate::interpreter::{RVEmu, RiscVHooks, Syscall, EMPTY_SHARED_MEMORY};
 use crate::{
     db::Database,
     handler::register::EvmHandler,
@@ -37,6 +39,11 @@ impl<DB: Database, INSP: Inspector<DB>> GetInspector<DB> for INSP {
 /// A few instructions handlers are wrapped twice once for `step` and `step_end`
 /// and in case of Logs and Selfdestruct wrapper is wrapped again for the
 /// `log` and `selfdestruct` calls.
+///
+/// RISC-V contracts do not use the instruction table, so their frames are executed
+/// by a wrapper around frame execution that calls `riscv_step`, `riscv_syscall` and
+/// `riscv_syscall_end`, and `log` and `selfdestruct` for the logs and selfdestructs of
+/// their syscalls.
 pub fn inspector_handle_register<DB: Database, EXT: GetInspector<DB>>(
     handler: &mut EvmHandler<'_, EXT, DB>,
 ) {
@@ -110,6 +117,38 @@ pub fn inspector_handle_register<DB: Database, EXT: GetInspector<DB>>(
         table.try_into().unwrap_or_else(|_| unreachable!()),
     ));
 
+    // RISC-V contracts do not use the instruction table, their frames are run with hooks
+    // that call the inspector instead. Logs and selfdestructs are made by syscalls.
+    #[cfg(feature = "riscv")]
+    {
+        let old_handle = handler.execution.execute_frame.clone();
+        handler.execution.execute_frame = Arc::new(
+            move |frame,
+                  shared_memory,
+                  instruction_tables,
+                  ctx|
+                  -> Result<_, EVMError<DB::Error>> {
+                if !frame.interpreter().contract.bytecode.is_riscv() {
+                    return old_handle(frame, shared_memory, instruction_tables, ctx);
+                }
+                let interpreter = frame.interpreter_mut();
+                let memory = core::mem::replace(shared_memory, EMPTY_SHARED_MEMORY);
+                let hooks = &mut InspectorRiscVHooks::default();
+                let next_action = match instruction_tables {
+                    InstructionTables::Plain(table) => {
+                        interpreter.run_with_riscv_hooks(memory, table, ctx, hooks)
+                    }
+                    InstructionTables::Boxed(table) => {
+                        interpreter.run_with_riscv_hooks(memory, table, ctx, hooks)
+                    }
+                };
+                // Take the shared memory back.
+                *shared_memory = interpreter.take_memory();
+                Ok(next_action)
+            },
+        );
+    }
+
     // call and create input stack shared between handlers. They are used to share
     // inputs in *_end Inspector calls.
     let call_input_stack = Rc::<RefCell<Vec<_>>>::new(RefCell::new(Vec::new()));
@@ -213,6 +252,84 @@ pub fn inspector_handle_register<DB: Database, EXT: GetInspector<DB>>(
     });
 }
 
+/// [`RiscVHooks`] that call the RISC-V callbacks of the inspector.
+#[cfg(feature = "riscv")]
+#[derive(Default)]
+struct InspectorRiscVHooks {
+    /// Number of logs before the current syscall.
+    old_log_len: usize,
+}
+
+#[cfg(feature = "riscv")]
+impl<DB: Database, EXT: GetInspector<DB>> RiscVHooks<Context<EXT, DB>> for InspectorRiscVHooks {
+    #[inline]
+    fn step(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        host: &mut Context<EXT, DB>,
+        pc: u64,
+        inst: u64,
+    ) {
+        host.external
+            .get_inspector()
+            .riscv_step(interp, riscv, &mut host.evm, pc, inst);
+    }
+
+    #[inline]
+    fn syscall(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        host: &mut Context<EXT, DB>,
+        syscall: Syscall,
+    ) {
+        self.old_log_len = host.evm.journaled_state.logs.len();
+        host.external
+            .get_inspector()
+            .riscv_syscall(interp, riscv, &mut host.evm, syscall);
+    }
+
+    #[inline]
+    fn syscall_end(
+        &mut self,
+        interp: &mut Interpreter,
+        riscv: &RVEmu,
+        host: &mut Context<EXT, DB>,
+        syscall: Syscall,
+    ) {
+        // check if log was added, like the `LOG` instructions do.
+        if matches!(
+            syscall,
+            Syscall::Log0 | Syscall::Log1 | Syscall::Log2 | Syscall::Log3 | Syscall::Log4
+        ) && host.evm.journaled_state.logs.len() == self.old_log_len + 1
+        {
+            let last_log = host.evm.journaled_state.logs.last().unwrap().clone();
+            host.external.get_inspector().log(&mut host.evm, &last_log);
+        }
+        // check if selfdestruct was successful and if journal entry is made, like the
+        // `SELFDESTRUCT` instruction does.
+        if syscall == Syscall::SelfDestruct
+            && interp.instruction_result == InstructionResult::SelfDestruct
+        {
+            if let Some(JournalEntry::AccountDestroyed {
+                address,
+                target,
+                had_balance,
+                ..
+            }) = host.evm.journaled_state.journal.last().unwrap().last()
+            {
+                host.external
+                    .get_inspector()
+                    .selfdestruct(*address, *target, *had_balance);
+            }
+        }
+        host.external
+            .get_inspector()
+            .riscv_syscall_end(interp, riscv, &mut host.evm, syscall);
+    }
+}
+
 /// Outer closure that calls Inspector for every instruction.
 pub fn inspector_instruction<
     'a,
@@ -251,6 +368,8 @@ pub fn inspector_instruction<
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "riscv")]
+    use crate::primitives::{Address, Log, U256};
     use crate::{
         db::EmptyDB,
         inspectors::NoOpInspector,
@@ -394,6 +513,200 @@ mod tests {
         assert!(inspector.call_end);
     }
 
+    #[cfg(feature = "riscv")]
+    #[derive(Default, Debug)]
+    struct LogInspector {
+        logs: Vec<Log>,
+    }
+
+    #[cfg(feature = "riscv")]
+    impl<DB: Database> Inspector<DB> for LogInspector {
+        fn log(&mut self, _context: &mut EvmContext<DB>, log: &Log) {
+            self.logs.push(log.clone());
+        }
+    }
+
+    #[cfg(feature = "riscv")]
+    #[test]
+    fn test_inspector_riscv_log() {
+        use crate::{
+            db::BenchmarkDB,
+            primitives::{address, Bytecode, TransactTo, B256, RISCV_MAGIC},
+        };
+
+        let mut code = vec![RISCV_MAGIC];
+        code.extend(std::fs::read("../../elf_test/log_example").unwrap());
+        let bytecode = Bytecode::new_riscv(code.into()).unwrap();
+
+        let mut evm: Evm<'_, LogInspector, BenchmarkDB> = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(LogInspector::default())
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        assert_eq!(result.logs().len(), 1);
+
+        let inspector = evm.into_context().external;
+        assert_eq!(inspector.logs, result.logs());
+        assert_eq!(
+            inspector.logs[0].topics(),
+            [B256::with_last_byte(1), B256::with_last_byte(2)]
+        );
+    }
+
+    #[cfg(feature = "riscv")]
+    #[derive(Default, Debug)]
+    struct SelfDestructInspector {
+        selfdestructs: Vec<(Address, Address, U256)>,
+    }
+
+    #[cfg(feature = "riscv")]
+    impl<DB: Database> Inspector<DB> for SelfDestructInspector {
+        fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+            self.selfdestructs.push((contract, target, value));
+        }
+    }
+
+    #[cfg(feature = "riscv")]
+    #[test]
+    fn test_inspector_riscv_selfdestruct() {
+        use crate::{
+            db::BenchmarkDB,
+            primitives::{address, Bytecode, SpecId, TransactTo, RISCV_MAGIC},
+        };
+
+        let mut code = vec![RISCV_MAGIC];
+        code.extend(std::fs::read("../../elf_test/selfdestruct_example").unwrap());
+        let bytecode = Bytecode::new_riscv(code.into()).unwrap();
+        let target = address!("2000000000000000000000000000000000000000");
+
+        // before Cancun, the contract is destroyed even if it was not created in the
+        // transaction.
+        let mut evm: Evm<'_, SelfDestructInspector, BenchmarkDB> = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(SelfDestructInspector::default())
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .with_spec_id(SpecId::SHANGHAI)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TransactTo::Call(Address::ZERO);
+                tx.data = target.into_word().0.into();
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+
+        let inspector = evm.into_context().external;
+        assert_eq!(
+            inspector.selfdestructs,
+            [(Address::ZERO, target, U256::from(10000000))]
+        );
+    }
+
+    #[cfg(feature = "riscv")]
+    #[derive(Default, Debug)]
+    struct RiscVInspector {
+        step: usize,
+        pcs: Vec<u64>,
+        syscalls: Vec<Syscall>,
+        syscall_ends: Vec<Syscall>,
+        gas_spent: Vec<u64>,
+    }
+
+    #[cfg(feature = "riscv")]
+    impl<DB: Database> Inspector<DB> for RiscVInspector {
+        fn step(&mut self, _interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+            self.step += 1;
+        }
+
+        fn riscv_step(
+            &mut self,
+            interp: &mut Interpreter,
+            _riscv: &RVEmu,
+            _context: &mut EvmContext<DB>,
+            pc: u64,
+            _inst: u64,
+        ) {
+            self.pcs.push(pc);
+            self.gas_spent.push(interp.gas.spent());
+        }
+
+        fn riscv_syscall(
+            &mut self,
+            _interp: &mut Interpreter,
+            riscv: &RVEmu,
+            _context: &mut EvmContext<DB>,
+            syscall: Syscall,
+        ) {
+            // t0 holds the syscall number.
+            assert_eq!(riscv.register(5), syscall as u64);
+            self.syscalls.push(syscall);
+        }
+
+        fn riscv_syscall_end(
+            &mut self,
+            _interp: &mut Interpreter,
+            _riscv: &RVEmu,
+            _context: &mut EvmContext<DB>,
+            syscall: Syscall,
+        ) {
+            self.syscall_ends.push(syscall);
+        }
+    }
+
+    #[cfg(feature = "riscv")]
+    #[test]
+    fn test_inspector_riscv_hooks() {
+        use crate::{
+            db::BenchmarkDB,
+            primitives::{address, Bytecode, TransactTo, RISCV_MAGIC},
+        };
+
+        let mut code = vec![RISCV_MAGIC];
+        code.extend(std::fs::read("../../elf_test/log_example").unwrap());
+        let bytecode = Bytecode::new_riscv(code.into()).unwrap();
+        let entry = bytecode.riscv().unwrap().entry();
+
+        let mut evm: Evm<'_, RiscVInspector, BenchmarkDB> = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(RiscVInspector::default())
+            .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to =
+                    TransactTo::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+
+        let inspector = evm.into_context().external;
+        assert_eq!(inspector.step, 0);
+        assert_eq!(inspector.pcs[0], entry);
+        assert!(inspector.pcs.windows(2).all(|pcs| pcs[0] < pcs[1]));
+        assert!(inspector.gas_spent.windows(2).all(|gas| gas[0] < gas[1]));
+        assert_eq!(inspector.syscalls, [Syscall::Log2, Syscall::Return]);
+        assert_eq!(inspector.syscall_ends, inspector.syscalls);
+    }
+
     #[test]
     fn test_inspector_reg() {
         let mut noop = NoOpInspector;
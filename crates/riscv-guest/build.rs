@@ -0,0 +1,10 @@
+use std::{env, fs, path::PathBuf};
+
+fn main() {
+    // put the linker script in the link search path of the guests depending on this crate,
+    // so they only have to pass `-C link-arg=-Triscv.ld`.
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::copy("riscv.ld", out.join("riscv.ld")).unwrap();
+    println!("cargo:rustc-link-search={}", out.display());
+    println!("cargo:rerun-if-changed=riscv.ld");
+}
@@ -0,0 +1,42 @@
+//! Caller contract used by the tests of `revm-riscv-guest`.
+//!
+//! Implements the Solidity interface below, setting and reading back a value of a storage
+//! contract with the interface of the `storage` guest. Failed calls revert with their
+//! output.
+#![no_std]
+#![no_main]
+
+use revm_riscv_guest::{abi::call_sol, dispatch, revert, sol, U256};
+
+sol! {
+    #![sol(alloy_sol_types = revm_riscv_guest::sol_types)]
+
+    interface IStorage {
+        function set(uint256 value) external;
+        function get() external view returns (uint256);
+    }
+
+    interface ICaller {
+        function setAndGet(address target, uint256 value) external returns (uint256);
+    }
+}
+
+use ICaller::setAndGetCall;
+use IStorage::{getCall, setCall};
+
+revm_riscv_guest::entry!(main);
+
+fn main() {
+    dispatch! {
+        setAndGetCall(call) => {
+            let set = setCall { value: call.value };
+            if let Err(output) = call_sol(u64::MAX, call.target, U256::ZERO, &set) {
+                revert(&output);
+            }
+            match call_sol(u64::MAX, call.target, U256::ZERO, &getCall {}) {
+                Ok(returns) => (returns._0,),
+                Err(output) => revert(&output),
+            }
+        },
+    }
+}
@@ -0,0 +1,20 @@
+//! Counter contract used by the tests of `revm-riscv-guest`.
+//!
+//! Adds the first calldata word to the counter in storage slot zero, logs the new value
+//! and returns it. Reverts with the counter if it would overflow.
+#![no_std]
+#![no_main]
+
+use revm_riscv_guest::{calldata_load, log, ret, revert, sload, sstore, B256, U256};
+
+revm_riscv_guest::entry!(main);
+
+fn main() {
+    let counter = sload(U256::ZERO);
+    let Some(counter) = counter.checked_add(calldata_load(0)) else {
+        revert(&counter.to_be_bytes::<32>());
+    };
+    sstore(U256::ZERO, counter);
+    log(&[], &[B256::from(counter)]);
+    ret(&counter.to_be_bytes::<32>());
+}
@@ -0,0 +1,27 @@
+//! Storage contract used by the tests of `revm-riscv-guest`.
+//!
+//! Implements the Solidity interface below, with the value in storage slot zero.
+#![no_std]
+#![no_main]
+
+use revm_riscv_guest::{dispatch, sload, sol, sstore, U256};
+
+sol! {
+    #![sol(alloy_sol_types = revm_riscv_guest::sol_types)]
+
+    interface IStorage {
+        function set(uint256 value) external;
+        function get() external view returns (uint256);
+    }
+}
+
+use IStorage::{getCall, setCall};
+
+revm_riscv_guest::entry!(main);
+
+fn main() {
+    dispatch! {
+        setCall(call) => sstore(U256::ZERO, call.value),
+        getCall(_) => (sload(U256::ZERO),),
+    }
+}
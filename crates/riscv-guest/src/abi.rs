@@ -0,0 +1,116 @@
+//! Solidity ABI support, so RISC-V contracts can be called like Solidity contracts.
+//!
+//! Calls are dispatched on their 4-byte selector with [`dispatch!`](crate::dispatch), and
+//! arguments and return values are encoded with [`alloy_sol_types`]. Types declared with
+//! [`sol!`](crate::sol) need the crate path if the guest does not depend on
+//! `alloy-sol-types` itself:
+//!
+//! ```ignore
+//! revm_riscv_guest::sol! {
+//!     #![sol(alloy_sol_types = revm_riscv_guest::sol_types)]
+//!     function get() external view returns (uint256);
+//! }
+//! ```
+
+use crate::{
+    call, calldata_copy, calldata_size, return_data_copy, return_data_size, revert, Address, U256,
+};
+use alloc::{vec, vec::Vec};
+use alloy_sol_types::{SolCall, SolError};
+
+/// Returns the calldata.
+#[inline]
+pub fn calldata() -> Vec<u8> {
+    let mut calldata = vec![0; calldata_size()];
+    calldata_copy(0, &mut calldata);
+    calldata
+}
+
+/// Returns the output of the last call or create.
+#[inline]
+pub fn return_data() -> Vec<u8> {
+    let mut data = vec![0; return_data_size()];
+    return_data_copy(0, &mut data);
+    data
+}
+
+/// Returns the 4-byte function selector of `calldata`, or `None` if it is shorter.
+#[inline]
+pub fn selector(calldata: &[u8]) -> Option<[u8; 4]> {
+    calldata.get(..4)?.try_into().ok()
+}
+
+/// Decodes `calldata` as a call to `C`, reverting with empty output like Solidity does if
+/// it is not a valid encoding.
+#[inline]
+pub fn decode_call<C: SolCall>(calldata: &[u8]) -> C {
+    match C::abi_decode(calldata, true) {
+        Ok(call) => call,
+        Err(_) => revert(&[]),
+    }
+}
+
+/// Reverts with `error` encoded as a Solidity custom error.
+#[inline]
+pub fn revert_error<E: SolError>(error: &E) -> ! {
+    revert(&error.abi_encode())
+}
+
+/// Calls function `C` of `address` with a gas limit of `gas` and `value`, see
+/// [`call`](crate::call).
+///
+/// Returns the decoded return values, or the output of the call if it failed. A successful
+/// call with output that does not decode also returns the output.
+#[inline]
+pub fn call_sol<C: SolCall>(
+    gas: u64,
+    address: Address,
+    value: U256,
+    function: &C,
+) -> Result<C::Return, Vec<u8>> {
+    let success = call(gas, address, value, &function.abi_encode(), &mut []);
+    let output = return_data();
+    if !success {
+        return Err(output);
+    }
+    C::abi_decode_returns(&output, true).map_err(|_| output)
+}
+
+/// Dispatches the call to the handler of the function with its selector, and returns the
+/// encoded return values of the handler.
+///
+/// Each handler is the name of a call type generated by [`sol!`](crate::sol), a pattern
+/// for the decoded call and an expression evaluating to the tuple of return values.
+/// Calls with an unknown selector or invalid arguments revert with empty output.
+///
+/// # Examples
+///
+/// ```ignore
+/// revm_riscv_guest::entry!(main);
+///
+/// fn main() {
+///     revm_riscv_guest::dispatch! {
+///         setCall(call) => sstore(U256::ZERO, call.value),
+///         getCall(_) => (sload(U256::ZERO),),
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! dispatch {
+    ($($call:ident($arg:pat) => $handler:expr),+ $(,)?) => {{
+        let calldata = $crate::abi::calldata();
+        let selector = $crate::abi::selector(&calldata);
+        $(
+            if selector == ::core::option::Option::Some(
+                <$call as $crate::sol_types::SolCall>::SELECTOR,
+            ) {
+                let $arg = $crate::abi::decode_call::<$call>(&calldata);
+                let returns = $handler;
+                $crate::ret(&<$call as $crate::sol_types::SolCall>::abi_encode_returns(
+                    &returns,
+                ));
+            }
+        )+
+        $crate::revert(&[])
+    }};
+}
@@ -0,0 +1,211 @@
+use crate::{
+    address_word,
+    syscall::{syscall, syscall_exit, Syscall},
+    word_syscall, Address, U256,
+};
+
+/// Returns from the contract with `data` as output.
+#[inline]
+pub fn ret(data: &[u8]) -> ! {
+    // SAFETY: the host only reads the data.
+    unsafe {
+        syscall_exit(
+            Syscall::Return,
+            [data.as_ptr() as u64, data.len() as u64, 0, 0],
+        )
+    }
+}
+
+/// Reverts the contract with `data` as output.
+#[inline]
+pub fn revert(data: &[u8]) -> ! {
+    // SAFETY: the host only reads the data.
+    unsafe {
+        syscall_exit(
+            Syscall::Revert,
+            [data.as_ptr() as u64, data.len() as u64, 0, 0],
+        )
+    }
+}
+
+/// Destroys the contract and sends its balance to `target`.
+///
+/// Since Cancun, the contract is only destroyed if it was created in the same transaction,
+/// otherwise only its balance is sent. Halts the contract if the call is static.
+#[inline]
+pub fn selfdestruct(target: Address) -> ! {
+    // SAFETY: takes no pointers.
+    unsafe { syscall_exit(Syscall::SelfDestruct, address_word(target).into_limbs()) }
+}
+
+/// Calls `address` with a gas limit of `gas`, `value` and `input`. Like the `CALL` opcode,
+/// the gas limit is capped to all but one 64th of the remaining gas, so `u64::MAX` forwards
+/// as much gas as possible.
+///
+/// Copies up to `output.len()` bytes of the output to `output` and returns whether the call
+/// succeeded. The whole output is read with [`return_data_copy`].
+#[inline]
+pub fn call(gas: u64, address: Address, value: U256, input: &[u8], output: &mut [u8]) -> bool {
+    let value = value.to_be_bytes::<32>();
+    let args = [
+        gas,
+        address.as_ptr() as u64,
+        value.as_ptr() as u64,
+        input.as_ptr() as u64,
+        input.len() as u64,
+        output.as_mut_ptr() as u64,
+        output.len() as u64,
+        0,
+    ];
+    // SAFETY: the host reads the address, the value and the input, and writes at most
+    // `output.len()` bytes to `output`.
+    unsafe { syscall(Syscall::Call, args)[0] != 0 }
+}
+
+/// Calls the code of `address` in the context of the executing contract, with a gas limit
+/// of `gas`, `value` and `input`. See [`call`] for the output.
+#[inline]
+pub fn call_code(gas: u64, address: Address, value: U256, input: &[u8], output: &mut [u8]) -> bool {
+    let value = value.to_be_bytes::<32>();
+    let args = [
+        gas,
+        address.as_ptr() as u64,
+        value.as_ptr() as u64,
+        input.as_ptr() as u64,
+        input.len() as u64,
+        output.as_mut_ptr() as u64,
+        output.len() as u64,
+        0,
+    ];
+    // SAFETY: see `call`.
+    unsafe { syscall(Syscall::CallCode, args)[0] != 0 }
+}
+
+/// Calls the code of `address` keeping the caller and value of the executing contract,
+/// with a gas limit of `gas` and `input`. See [`call`] for the output.
+#[inline]
+pub fn delegate_call(gas: u64, address: Address, input: &[u8], output: &mut [u8]) -> bool {
+    let args = [
+        gas,
+        address.as_ptr() as u64,
+        input.as_ptr() as u64,
+        input.len() as u64,
+        output.as_mut_ptr() as u64,
+        output.len() as u64,
+        0,
+        0,
+    ];
+    // SAFETY: see `call`.
+    unsafe { syscall(Syscall::DelegateCall, args)[0] != 0 }
+}
+
+/// Calls `address` without allowing state changes, with a gas limit of `gas` and `input`.
+/// See [`call`] for the output.
+#[inline]
+pub fn static_call(gas: u64, address: Address, input: &[u8], output: &mut [u8]) -> bool {
+    let args = [
+        gas,
+        address.as_ptr() as u64,
+        input.as_ptr() as u64,
+        input.len() as u64,
+        output.as_mut_ptr() as u64,
+        output.len() as u64,
+        0,
+        0,
+    ];
+    // SAFETY: see `call`.
+    unsafe { syscall(Syscall::StaticCall, args)[0] != 0 }
+}
+
+/// Runs the precompile at `address` with `input` and a gas limit of `gas`, without the
+/// cost of a call.
+///
+/// Copies up to `output.len()` bytes of the output to `output` and returns whether the
+/// precompile succeeded. Fails without consuming gas if there is no precompile at
+/// `address`. The whole output is read with [`return_data_copy`].
+#[inline]
+pub fn precompile(gas: u64, address: Address, input: &[u8], output: &mut [u8]) -> bool {
+    let args = [
+        address.as_ptr() as u64,
+        input.as_ptr() as u64,
+        input.len() as u64,
+        gas,
+        output.as_mut_ptr() as u64,
+        output.len() as u64,
+        0,
+        0,
+    ];
+    // SAFETY: see `call`.
+    unsafe { syscall(Syscall::Precompile, args)[0] != 0 }
+}
+
+/// Creates a contract with `value` and `init_code`, and returns its address, or `None` if
+/// the creation failed.
+#[inline]
+pub fn create(value: U256, init_code: &[u8]) -> Option<Address> {
+    let value = value.to_be_bytes::<32>();
+    let args = [
+        value.as_ptr() as u64,
+        init_code.as_ptr() as u64,
+        init_code.len() as u64,
+        0,
+        0,
+        0,
+        0,
+        0,
+    ];
+    // SAFETY: the host only reads the value and the initcode.
+    created(unsafe { syscall(Syscall::Create, args) })
+}
+
+/// Creates a contract with `value` and `init_code` at the address derived from `salt`,
+/// and returns its address, or `None` if the creation failed.
+#[inline]
+pub fn create2(value: U256, init_code: &[u8], salt: U256) -> Option<Address> {
+    let value = value.to_be_bytes::<32>();
+    let [s0, s1, s2, s3] = salt.into_limbs();
+    let args = [
+        value.as_ptr() as u64,
+        init_code.as_ptr() as u64,
+        init_code.len() as u64,
+        s0,
+        s1,
+        s2,
+        s3,
+        0,
+    ];
+    // SAFETY: the host only reads the value and the initcode.
+    created(unsafe { syscall(Syscall::Create2, args) })
+}
+
+/// Returns the address written back by a create syscall, zero if it failed.
+#[inline(always)]
+fn created(limbs: [u64; 4]) -> Option<Address> {
+    let address = Address::from_word(U256::from_limbs(limbs).into());
+    (!address.is_zero()).then_some(address)
+}
+
+/// Size of the output of the last call or create.
+#[inline]
+pub fn return_data_size() -> usize {
+    word_syscall(Syscall::ReturnDataSize, [0; 8]).to()
+}
+
+/// Copies the output of the last call or create starting at `offset` into `dest`.
+///
+/// Halts the contract if the range is past the end of the output.
+#[inline]
+pub fn return_data_copy(offset: usize, dest: &mut [u8]) {
+    let args = [
+        dest.as_mut_ptr() as u64,
+        offset as u64,
+        dest.len() as u64,
+        0,
+        0,
+        0,
+        0,
+        0,
+    ];
+    // SAFETY: the host writes at most `dest.len()` bytes to `dest`.
+    unsafe { syscall(Syscall::ReturnDataCopy, args) };
+}
@@ -0,0 +1,185 @@
+use crate::{
+    address_word,
+    syscall::{syscall, Syscall},
+    word_args, word_syscall, Address, B256, U256,
+};
+
+/// Returns the address in the word of `syscall`, which takes no arguments.
+#[inline(always)]
+fn address_syscall(syscall: Syscall) -> Address {
+    Address::from_word(word_syscall(syscall, [0; 8]).into())
+}
+
+/// Address of the executing contract.
+#[inline]
+pub fn address() -> Address {
+    address_syscall(Syscall::Address)
+}
+
+/// Balance of `address`.
+#[inline]
+pub fn balance(address: Address) -> U256 {
+    word_syscall(Syscall::Balance, word_args(address_word(address), [0; 4]))
+}
+
+/// Origin of the transaction.
+#[inline]
+pub fn origin() -> Address {
+    address_syscall(Syscall::Origin)
+}
+
+/// Caller of the executing contract.
+#[inline]
+pub fn caller() -> Address {
+    address_syscall(Syscall::Caller)
+}
+
+/// Value sent with the call.
+#[inline]
+pub fn call_value() -> U256 {
+    word_syscall(Syscall::CallValue, [0; 8])
+}
+
+/// Calldata word at `offset`, padded with zeros past the end of the calldata.
+#[inline]
+pub fn calldata_load(offset: usize) -> U256 {
+    word_syscall(Syscall::CallDataLoad, word_args(U256::from(offset), [0; 4]))
+}
+
+/// Size of the calldata.
+#[inline]
+pub fn calldata_size() -> usize {
+    word_syscall(Syscall::CallDataSize, [0; 8]).to()
+}
+
+/// Copies calldata starting at `offset` into `dest`, with zeros past the end of the
+/// calldata.
+#[inline]
+pub fn calldata_copy(offset: usize, dest: &mut [u8]) {
+    // SAFETY: the host writes at most `dest.len()` bytes to `dest`.
+    unsafe {
+        syscall(
+            Syscall::CallDataCopy,
+            [
+                dest.as_mut_ptr() as u64,
+                offset as u64,
+                dest.len() as u64,
+                0,
+                0,
+                0,
+                0,
+                0,
+            ],
+        )
+    };
+}
+
+/// Effective gas price of the transaction.
+#[inline]
+pub fn gas_price() -> U256 {
+    word_syscall(Syscall::GasPrice, [0; 8])
+}
+
+/// Code size of `address`.
+#[inline]
+pub fn ext_code_size(address: Address) -> usize {
+    word_syscall(
+        Syscall::ExtCodeSize,
+        word_args(address_word(address), [0; 4]),
+    )
+    .to()
+}
+
+/// Copies the code of `address` starting at `offset` into `dest`, with zeros past the end
+/// of the code.
+#[inline]
+pub fn ext_code_copy(address: Address, offset: usize, dest: &mut [u8]) {
+    let args = word_args(
+        address_word(address),
+        [
+            dest.as_mut_ptr() as u64,
+            offset as u64,
+            dest.len() as u64,
+            0,
+        ],
+    );
+    // SAFETY: the host writes at most `dest.len()` bytes to `dest`.
+    unsafe { syscall(Syscall::ExtCodeCopy, args) };
+}
+
+/// Code hash of `address`.
+#[inline]
+pub fn ext_code_hash(address: Address) -> B256 {
+    word_syscall(
+        Syscall::ExtCodeHash,
+        word_args(address_word(address), [0; 4]),
+    )
+    .into()
+}
+
+/// Hash of block `number`, zero if it is not one of the last 256 blocks.
+#[inline]
+pub fn block_hash(number: u64) -> B256 {
+    word_syscall(Syscall::BlockHash, word_args(U256::from(number), [0; 4])).into()
+}
+
+/// Beneficiary of the block.
+#[inline]
+pub fn coinbase() -> Address {
+    address_syscall(Syscall::Coinbase)
+}
+
+/// Timestamp of the block.
+#[inline]
+pub fn timestamp() -> U256 {
+    word_syscall(Syscall::Timestamp, [0; 8])
+}
+
+/// Number of the block.
+#[inline]
+pub fn number() -> U256 {
+    word_syscall(Syscall::Number, [0; 8])
+}
+
+/// Prevrandao of the block, or its difficulty before the merge.
+#[inline]
+pub fn prev_randao() -> B256 {
+    word_syscall(Syscall::PrevRandao, [0; 8]).into()
+}
+
+/// Gas limit of the block.
+#[inline]
+pub fn gas_limit() -> U256 {
+    word_syscall(Syscall::GasLimit, [0; 8])
+}
+
+/// Chain id.
+#[inline]
+pub fn chain_id() -> u64 {
+    word_syscall(Syscall::ChainId, [0; 8]).to()
+}
+
+/// Balance of the executing contract.
+#[inline]
+pub fn self_balance() -> U256 {
+    word_syscall(Syscall::SelfBalance, [0; 8])
+}
+
+/// Base fee of the block.
+#[inline]
+pub fn base_fee() -> U256 {
+    word_syscall(Syscall::BaseFee, [0; 8])
+}
+
+/// Versioned hash of the blob at `index` of the transaction, zero if there is no such
+/// blob.
+#[inline]
+pub fn blob_hash(index: usize) -> B256 {
+    word_syscall(Syscall::BlobHash, word_args(U256::from(index), [0; 4])).into()
+}
+
+/// Blob base fee of the block.
+#[inline]
+pub fn blob_base_fee() -> U256 {
+    word_syscall(Syscall::BlobBaseFee, [0; 8])
+}
@@ -0,0 +1,47 @@
+//! Global allocator of RISC-V guests.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    ptr,
+};
+
+extern "C" {
+    /// Start of the heap, placed past the stack by the linker script.
+    static __heap_start: u8;
+}
+
+/// Allocator that hands out memory past the stack in order and never frees it.
+///
+/// Contracts are short-lived and the interpreter charges for guest memory by its highest
+/// accessed address, so reusing freed memory would save little.
+struct BumpAllocator {
+    /// Address of the next free byte, zero before the first allocation.
+    next: UnsafeCell<usize>,
+}
+
+// SAFETY: guests are single-threaded.
+unsafe impl Sync for BumpAllocator {}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let next = &mut *self.next.get();
+        if *next == 0 {
+            *next = ptr::addr_of!(__heap_start) as usize;
+        }
+        // the alignment is a power of two.
+        let start = (*next + layout.align() - 1) & !(layout.align() - 1);
+        let Some(end) = start.checked_add(layout.size()) else {
+            return ptr::null_mut();
+        };
+        *next = end;
+        start as *mut u8
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator {
+    next: UnsafeCell::new(0),
+};
@@ -0,0 +1,110 @@
+//! # revm-riscv-guest
+//!
+//! Bindings to the syscalls of RISC-V contracts run by revm.
+//!
+//! A contract is a `no_std`, `no_main` binary for `riscv64imac-unknown-none-elf` that
+//! declares its entry point with [`entry!`] and is linked with the `riscv.ld` script of
+//! this crate, which is put in the link search path by its build script:
+//!
+//! ```toml
+//! # .cargo/config.toml
+//! [build]
+//! target = "riscv64imac-unknown-none-elf"
+//!
+//! [target.riscv64imac-unknown-none-elf]
+//! rustflags = ["-C", "link-arg=-Triscv.ld"]
+//! ```
+//!
+//! The resulting ELF, prefixed with the RISC-V magic byte, is the contract code. Memory
+//! allocated by the guest is never freed, and contracts that follow the Solidity ABI are
+//! written with the helpers of the [`abi`] module.
+//!
+//! On other targets the crate compiles, but every syscall panics.
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![no_std]
+
+extern crate alloc;
+
+pub mod abi;
+mod call;
+mod context;
+#[cfg(all(target_arch = "riscv64", target_os = "none"))]
+mod heap;
+mod state;
+pub mod syscall;
+
+pub use alloy_primitives::{self, address, Address, B256, U256};
+pub use alloy_sol_types::{self as sol_types, sol};
+pub use call::*;
+pub use context::*;
+pub use state::*;
+
+/// Declares the entry point of a RISC-V contract.
+///
+/// `main` is called with no arguments, the calldata is read with [`calldata_load`] and
+/// [`calldata_copy`]. If it returns, the contract returns with empty output.
+///
+/// # Examples
+///
+/// ```ignore
+/// #![no_std]
+/// #![no_main]
+///
+/// revm_riscv_guest::entry!(main);
+///
+/// fn main() {
+///     let value = revm_riscv_guest::calldata_load(0);
+///     revm_riscv_guest::ret(&value.to_be_bytes::<32>());
+/// }
+/// ```
+#[macro_export]
+macro_rules! entry {
+    ($main:path) => {
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn __revm_riscv_main() -> ! {
+            let main: fn() = $main;
+            main();
+            $crate::ret(&[])
+        }
+    };
+}
+
+// `_start` sets up the stack, the rest of the memory is zeroed by the interpreter when
+// the program is loaded.
+#[cfg(all(target_arch = "riscv64", target_os = "none"))]
+core::arch::global_asm!(
+    ".section .text._start, \"ax\"",
+    ".globl _start",
+    "_start:",
+    "    la sp, __stack_top",
+    "    call __revm_riscv_main",
+);
+
+/// Reverts with empty output.
+#[cfg(all(target_arch = "riscv64", target_os = "none"))]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo<'_>) -> ! {
+    revert(&[])
+}
+
+/// Returns the limbs of `word` in `a0..a3` followed by `rest` in `a4..a7`.
+#[inline(always)]
+fn word_args(word: U256, rest: [u64; 4]) -> [u64; 8] {
+    let [a0, a1, a2, a3] = word.into_limbs();
+    let [a4, a5, a6, a7] = rest;
+    [a0, a1, a2, a3, a4, a5, a6, a7]
+}
+
+/// Returns `address` as a word, the way syscalls take and return addresses.
+#[inline(always)]
+fn address_word(address: Address) -> U256 {
+    address.into_word().into()
+}
+
+/// Makes `syscall` and returns the word in `a0..a3`.
+#[inline(always)]
+fn word_syscall(syscall: syscall::Syscall, args: [u64; 8]) -> U256 {
+    // SAFETY: only used for syscalls that take no pointers.
+    U256::from_limbs(unsafe { syscall::syscall(syscall, args) })
+}
@@ -0,0 +1,80 @@
+use crate::{
+    syscall::{syscall, Syscall},
+    word_args, word_syscall, B256, U256,
+};
+
+/// Keccak-256 hash of `data`.
+#[inline]
+pub fn keccak256(data: &[u8]) -> B256 {
+    let args = [data.as_ptr() as u64, data.len() as u64, 0, 0, 0, 0, 0, 0];
+    // SAFETY: the host only reads the data.
+    let limbs = unsafe { syscall(Syscall::Keccak256, args) };
+    U256::from_limbs(limbs).into()
+}
+
+/// Loads the storage word of the executing contract under `key`.
+#[inline]
+pub fn sload(key: U256) -> U256 {
+    word_syscall(Syscall::SLoad, word_args(key, [0; 4]))
+}
+
+/// Stores `value` under `key` in the storage of the executing contract.
+///
+/// Halts the contract if the call is static.
+#[inline]
+pub fn sstore(key: U256, value: U256) {
+    word_syscall(Syscall::SStore, word_args(key, value.into_limbs()));
+}
+
+/// Loads the transient storage word of the executing contract under `key`.
+#[inline]
+pub fn tload(key: U256) -> U256 {
+    word_syscall(Syscall::TLoad, word_args(key, [0; 4]))
+}
+
+/// Stores `value` under `key` in the transient storage of the executing contract, which
+/// is cleared at the end of the transaction.
+///
+/// Halts the contract if the call is static.
+#[inline]
+pub fn tstore(key: U256, value: U256) {
+    word_syscall(Syscall::TStore, word_args(key, value.into_limbs()));
+}
+
+/// Emits a log with `data` and `topics`.
+///
+/// # Panics
+///
+/// Panics if there are more than four topics.
+#[inline]
+pub fn log(data: &[u8], topics: &[B256]) {
+    let syscall_number = match topics.len() {
+        0 => Syscall::Log0,
+        1 => Syscall::Log1,
+        2 => Syscall::Log2,
+        3 => Syscall::Log3,
+        4 => Syscall::Log4,
+        _ => panic!("a log has at most four topics"),
+    };
+    let mut pointers = [0; 4];
+    for (pointer, topic) in pointers.iter_mut().zip(topics) {
+        *pointer = topic.as_ptr() as u64;
+    }
+    let [a2, a3, a4, a5] = pointers;
+    // SAFETY: the host only reads the data and the topics.
+    unsafe {
+        syscall(
+            syscall_number,
+            [
+                data.as_ptr() as u64,
+                data.len() as u64,
+                a2,
+                a3,
+                a4,
+                a5,
+                0,
+                0,
+            ],
+        )
+    };
+}
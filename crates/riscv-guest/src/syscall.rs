@@ -0,0 +1,125 @@
+//! Raw syscall interface of RISC-V contracts.
+//!
+//! The syscall number is passed in `t0` and the arguments in `a0..a7`. Words are passed and
+//! returned as four little-endian 64-bit limbs in consecutive registers, starting with the
+//! least significant limb. See the `Syscall` type of `revm-interpreter` for the registers
+//! each syscall reads and writes.
+
+/// Syscalls a RISC-V contract can make, numbered like the interpreter expects them.
+///
+/// Apart from the first six, syscalls are numbered after the opcode they mirror.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Syscall {
+    Return = 0,
+    SLoad = 1,
+    SStore = 2,
+    Call = 3,
+    Revert = 4,
+    Precompile = 5,
+    Keccak256 = 0x20,
+    Address = 0x30,
+    Balance = 0x31,
+    Origin = 0x32,
+    Caller = 0x33,
+    CallValue = 0x34,
+    CallDataLoad = 0x35,
+    CallDataSize = 0x36,
+    CallDataCopy = 0x37,
+    GasPrice = 0x3A,
+    ExtCodeSize = 0x3B,
+    ExtCodeCopy = 0x3C,
+    ReturnDataSize = 0x3D,
+    ReturnDataCopy = 0x3E,
+    ExtCodeHash = 0x3F,
+    BlockHash = 0x40,
+    Coinbase = 0x41,
+    Timestamp = 0x42,
+    Number = 0x43,
+    PrevRandao = 0x44,
+    GasLimit = 0x45,
+    ChainId = 0x46,
+    SelfBalance = 0x47,
+    BaseFee = 0x48,
+    BlobHash = 0x49,
+    BlobBaseFee = 0x4A,
+    TLoad = 0x5C,
+    TStore = 0x5D,
+    Log0 = 0xA0,
+    Log1 = 0xA1,
+    Log2 = 0xA2,
+    Log3 = 0xA3,
+    Log4 = 0xA4,
+    Create = 0xF0,
+    CallCode = 0xF2,
+    DelegateCall = 0xF4,
+    Create2 = 0xF5,
+    StaticCall = 0xFA,
+    SelfDestruct = 0xFF,
+}
+
+/// Makes `syscall` with `args` in `a0..a7` and returns `a0..a3`.
+///
+/// # Safety
+///
+/// The arguments must be valid for the syscall: the host reads and writes guest memory
+/// through the pointers passed to it.
+#[inline(always)]
+pub unsafe fn syscall(syscall: Syscall, args: [u64; 8]) -> [u64; 4] {
+    #[cfg(target_arch = "riscv64")]
+    {
+        let [mut a0, mut a1, mut a2, mut a3, a4, a5, a6, a7] = args;
+        core::arch::asm!(
+            "ecall",
+            in("t0") syscall as u64,
+            inout("a0") a0,
+            inout("a1") a1,
+            inout("a2") a2,
+            inout("a3") a3,
+            in("a4") a4,
+            in("a5") a5,
+            in("a6") a6,
+            in("a7") a7,
+            options(nostack),
+        );
+        [a0, a1, a2, a3]
+    }
+    #[cfg(not(target_arch = "riscv64"))]
+    {
+        let _ = args;
+        unsupported(syscall)
+    }
+}
+
+/// Makes `syscall`, which ends the execution, with `args` in `a0..a3`.
+///
+/// # Safety
+///
+/// See [`syscall`].
+#[inline(always)]
+pub unsafe fn syscall_exit(syscall: Syscall, args: [u64; 4]) -> ! {
+    #[cfg(target_arch = "riscv64")]
+    {
+        core::arch::asm!(
+            "ecall",
+            in("t0") syscall as u64,
+            in("a0") args[0],
+            in("a1") args[1],
+            in("a2") args[2],
+            in("a3") args[3],
+            options(noreturn, nostack),
+        )
+    }
+    #[cfg(not(target_arch = "riscv64"))]
+    {
+        let _ = args;
+        unsupported(syscall)
+    }
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[cold]
+fn unsupported(syscall: Syscall) -> ! {
+    panic!("{syscall:?} syscall is only available to RISC-V guests")
+}
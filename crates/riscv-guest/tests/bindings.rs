@@ -0,0 +1,85 @@
+//! Checks the bindings of the guest on the host, without building a guest.
+
+use revm_riscv_guest::{dispatch, sload, sol, sstore, syscall::Syscall, U256};
+
+sol! {
+    #![sol(alloy_sol_types = revm_riscv_guest::sol_types)]
+
+    interface IStorage {
+        function set(uint256 value) external;
+        function get() external view returns (uint256);
+    }
+}
+
+use IStorage::{getCall, setCall};
+
+/// Dispatcher of the `storage` guest, built on the host to check the expansion of
+/// [`dispatch!`].
+#[allow(dead_code)]
+fn storage_main() {
+    dispatch! {
+        setCall(call) => sstore(U256::ZERO, call.value),
+        getCall(_) => (sload(U256::ZERO),),
+    }
+}
+
+#[test]
+fn syscall_numbers() {
+    use revm_interpreter::Syscall as Host;
+
+    let syscalls = [
+        (Syscall::Return, Host::Return),
+        (Syscall::SLoad, Host::SLoad),
+        (Syscall::SStore, Host::SStore),
+        (Syscall::Call, Host::Call),
+        (Syscall::Revert, Host::Revert),
+        (Syscall::Precompile, Host::Precompile),
+        (Syscall::Keccak256, Host::Keccak256),
+        (Syscall::Address, Host::Address),
+        (Syscall::Balance, Host::Balance),
+        (Syscall::Origin, Host::Origin),
+        (Syscall::Caller, Host::Caller),
+        (Syscall::CallValue, Host::CallValue),
+        (Syscall::CallDataLoad, Host::CallDataLoad),
+        (Syscall::CallDataSize, Host::CallDataSize),
+        (Syscall::CallDataCopy, Host::CallDataCopy),
+        (Syscall::GasPrice, Host::GasPrice),
+        (Syscall::ExtCodeSize, Host::ExtCodeSize),
+        (Syscall::ExtCodeCopy, Host::ExtCodeCopy),
+        (Syscall::ReturnDataSize, Host::ReturnDataSize),
+        (Syscall::ReturnDataCopy, Host::ReturnDataCopy),
+        (Syscall::ExtCodeHash, Host::ExtCodeHash),
+        (Syscall::BlockHash, Host::BlockHash),
+        (Syscall::Coinbase, Host::Coinbase),
+        (Syscall::Timestamp, Host::Timestamp),
+        (Syscall::Number, Host::Number),
+        (Syscall::PrevRandao, Host::PrevRandao),
+        (Syscall::GasLimit, Host::GasLimit),
+        (Syscall::ChainId, Host::ChainId),
+        (Syscall::SelfBalance, Host::SelfBalance),
+        (Syscall::BaseFee, Host::BaseFee),
+        (Syscall::BlobHash, Host::BlobHash),
+        (Syscall::BlobBaseFee, Host::BlobBaseFee),
+        (Syscall::TLoad, Host::TLoad),
+        (Syscall::TStore, Host::TStore),
+        (Syscall::Log0, Host::Log0),
+        (Syscall::Log1, Host::Log1),
+        (Syscall::Log2, Host::Log2),
+        (Syscall::Log3, Host::Log3),
+        (Syscall::Log4, Host::Log4),
+        (Syscall::Create, Host::Create),
+        (Syscall::CallCode, Host::CallCode),
+        (Syscall::DelegateCall, Host::DelegateCall),
+        (Syscall::Create2, Host::Create2),
+        (Syscall::StaticCall, Host::StaticCall),
+        (Syscall::SelfDestruct, Host::SelfDestruct),
+    ];
+    for (guest, host) in syscalls {
+        assert_eq!(Host::from_u64(guest as u64), Some(host), "{guest:?}");
+    }
+    // every syscall the interpreter knows has a binding.
+    let known = (0..=u8::MAX as u64)
+        .filter(|&number| Host::from_u64(number).is_some())
+        .count();
+    assert_eq!(known, syscalls.len());
+}
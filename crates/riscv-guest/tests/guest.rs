@@ -0,0 +1,248 @@
+//! Builds the guests in `guests` and runs them in the interpreter.
+//!
+//! Building the guests needs the `riscv64imac-unknown-none-elf` target, so these tests only
+//! run with the `build-guests` feature:
+//!
+//! ```sh
+//! rustup target add riscv64imac-unknown-none-elf
+//! cargo test -p revm-riscv-guest --features build-guests
+//! ```
+
+use revm::{
+    db::InMemoryDB,
+    primitives::{AccountInfo, ExecutionResult, Output, SuccessReason, TransactTo},
+    Database, Evm,
+};
+use revm_interpreter::{
+    opcode::make_instruction_table,
+    primitives::{Address, Bytecode, Bytes, CancunSpec, SpecId, B256, RISCV_MAGIC, U256},
+    Contract, DummyHost, InstructionResult, Interpreter, InterpreterAction, InterpreterResult,
+    EMPTY_SHARED_MEMORY,
+};
+use revm_riscv_guest::{address, sol, sol_types::SolCall};
+use std::{fs, path::Path, process::Command};
+
+sol! {
+    #![sol(alloy_sol_types = revm_riscv_guest::sol_types)]
+
+    interface IStorage {
+        function set(uint256 value) external;
+        function get() external view returns (uint256);
+    }
+
+    interface ICaller {
+        function setAndGet(address target, uint256 value) external returns (uint256);
+    }
+}
+
+use ICaller::setAndGetCall;
+use IStorage::{getCall, setCall};
+
+const TARGET: &str = "riscv64imac-unknown-none-elf";
+
+/// Builds the guest `name` in release mode and returns its contract code.
+fn build_guest(name: &str) -> Bytecode {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("guests")
+        .join(name);
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--release"])
+        .current_dir(&dir)
+        .env_remove("RUSTFLAGS")
+        .env_remove("CARGO_TARGET_DIR")
+        .env_remove("CARGO_BUILD_TARGET")
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to build guest {name}");
+
+    let mut code = vec![RISCV_MAGIC];
+    code.extend(fs::read(dir.join("target").join(TARGET).join("release").join(name)).unwrap());
+    Bytecode::new_riscv(code.into()).unwrap()
+}
+
+fn run(bytecode: &Bytecode, input: impl Into<Bytes>, host: &mut DummyHost) -> InterpreterResult {
+    let contract = Contract::new(
+        input.into(),
+        bytecode.clone(),
+        None,
+        Address::default(),
+        Address::default(),
+        U256::ZERO,
+    );
+    let mut interp = Interpreter::new(contract, 1_000_000, false);
+    interp.set_spec_id(SpecId::CANCUN);
+    let table = make_instruction_table::<DummyHost, CancunSpec>();
+    match interp.run(EMPTY_SHARED_MEMORY, &table, host) {
+        InterpreterAction::Return { result } => result,
+        action => panic!("Expected return action, got {action:?}"),
+    }
+}
+
+fn word(value: u64) -> Bytes {
+    Bytes::copy_from_slice(&U256::from(value).to_be_bytes::<32>())
+}
+
+#[test]
+fn counter() {
+    let bytecode = build_guest("counter");
+    let mut host = DummyHost::default();
+
+    let result = run(&bytecode, word(2), &mut host);
+    assert_eq!(result.result, InstructionResult::Return);
+    assert_eq!(result.output[..], U256::from(2).to_be_bytes::<32>());
+
+    let result = run(&bytecode, word(3), &mut host);
+    assert_eq!(result.result, InstructionResult::Return);
+    assert_eq!(result.output[..], U256::from(5).to_be_bytes::<32>());
+    assert_eq!(host.storage[&U256::ZERO], U256::from(5));
+    assert_eq!(host.log.len(), 2);
+    assert_eq!(host.log[1].topics(), [B256::from(U256::from(5))]);
+
+    // overflows revert with the counter.
+    let result = run(&bytecode, U256::MAX.to_be_bytes_vec(), &mut host);
+    assert_eq!(result.result, InstructionResult::Revert);
+    assert_eq!(result.output[..], U256::from(5).to_be_bytes::<32>());
+    assert_eq!(host.storage[&U256::ZERO], U256::from(5));
+}
+
+#[test]
+fn storage() {
+    let bytecode = build_guest("storage");
+    let mut host = DummyHost::default();
+
+    let value = U256::from(7);
+    let result = run(&bytecode, setCall { value }.abi_encode(), &mut host);
+    assert_eq!(result.result, InstructionResult::Return);
+    assert!(result.output.is_empty());
+    assert_eq!(host.storage[&U256::ZERO], value);
+
+    let result = run(&bytecode, getCall {}.abi_encode(), &mut host);
+    assert_eq!(result.result, InstructionResult::Return);
+    let returns = getCall::abi_decode_returns(&result.output, true).unwrap();
+    assert_eq!(returns._0, value);
+
+    // unknown selectors and invalid arguments revert with empty output.
+    for input in [
+        vec![0xde, 0xad, 0xbe, 0xef],
+        vec![],
+        setCall::SELECTOR.to_vec(),
+    ] {
+        let result = run(&bytecode, input, &mut host);
+        assert_eq!(result.result, InstructionResult::Revert);
+        assert!(result.output.is_empty());
+    }
+}
+
+/// Runtime code of `setAndGet` of the contract below, hand-assembled without the selector
+/// dispatch and the checks on the arguments:
+///
+/// ```solidity
+/// contract Caller {
+///     function setAndGet(IStorage target, uint256 value) external returns (uint256) {
+///         target.set(value);
+///         return target.get();
+///     }
+/// }
+/// ```
+///
+/// Failed calls revert with their output.
+fn caller_code() -> Bytecode {
+    use revm::interpreter::opcode::*;
+
+    let selector = |call: [u8; 4]| [PUSH4, call[0], call[1], call[2], call[3]];
+    let mut code = Vec::new();
+    let mut jumps = Vec::new();
+
+    // target.set(value)
+    code.extend(selector(setCall::SELECTOR));
+    code.extend([PUSH1, 0xE0, SHL, PUSH1, 0, MSTORE]);
+    code.extend([PUSH1, 36, CALLDATALOAD, PUSH1, 4, MSTORE]);
+    code.extend([PUSH1, 0, PUSH1, 0, PUSH1, 36, PUSH1, 0, PUSH1, 0]);
+    code.extend([PUSH1, 4, CALLDATALOAD, GAS, CALL, ISZERO, PUSH1]);
+    jumps.push(code.len());
+    code.extend([0, JUMPI]);
+
+    // return target.get()
+    code.extend(selector(getCall::SELECTOR));
+    code.extend([PUSH1, 0xE0, SHL, PUSH1, 0, MSTORE]);
+    code.extend([PUSH1, 32, PUSH1, 0, PUSH1, 4, PUSH1, 0]);
+    code.extend([PUSH1, 4, CALLDATALOAD, GAS, STATICCALL, ISZERO, PUSH1]);
+    jumps.push(code.len());
+    code.extend([0, JUMPI]);
+    code.extend([PUSH1, 32, PUSH1, 0, RETURN]);
+
+    // revert with the output of the failed call
+    for jump in jumps {
+        code[jump] = code.len() as u8;
+    }
+    code.extend([JUMPDEST, RETURNDATASIZE, PUSH1, 0, PUSH1, 0, RETURNDATACOPY]);
+    code.extend([RETURNDATASIZE, PUSH1, 0, REVERT]);
+    Bytecode::new_raw(code.into())
+}
+
+/// Commits a transaction calling `to` with `input`.
+fn transact(evm: &mut Evm<'_, (), InMemoryDB>, to: Address, input: Vec<u8>) -> ExecutionResult {
+    evm.context.evm.env.tx.transact_to = TransactTo::Call(to);
+    evm.context.evm.env.tx.data = input.into();
+    evm.transact_commit().unwrap()
+}
+
+/// Returns the output of a successful transaction.
+fn returned(result: ExecutionResult) -> Bytes {
+    match result {
+        ExecutionResult::Success {
+            reason: SuccessReason::Return,
+            output: Output::Call(output),
+            ..
+        } => output,
+        result => panic!("transaction failed: {result:?}"),
+    }
+}
+
+#[test]
+fn solidity_calls_riscv() {
+    let evm_caller = address!("1000000000000000000000000000000000000001");
+    let riscv_caller = address!("2000000000000000000000000000000000000002");
+    let storage = address!("3000000000000000000000000000000000000003");
+    let contracts = [
+        (evm_caller, caller_code()),
+        (riscv_caller, build_guest("caller")),
+        (storage, build_guest("storage")),
+    ];
+    let mut evm = Evm::builder()
+        .with_db(InMemoryDB::default())
+        .modify_db(|db| {
+            for (address, code) in contracts {
+                let info = AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code);
+                db.insert_account_info(address, info);
+            }
+        })
+        .modify_cfg_env(|cfg| cfg.enable_riscv = true)
+        .modify_tx_env(|tx| tx.gas_limit = 1_000_000)
+        .build();
+
+    // the EVM contract calls the guest with the ABI, and so does the guest calling it with
+    // `call_sol`.
+    for (caller, value) in [(evm_caller, 0x1234u64), (riscv_caller, 0x5678)] {
+        let value = U256::from(value) << 128;
+        let input = setAndGetCall {
+            target: storage,
+            value,
+        }
+        .abi_encode();
+        let output = returned(transact(&mut evm, caller, input));
+        let returns = setAndGetCall::abi_decode_returns(&output, true).unwrap();
+        assert_eq!(returns._0, value);
+        assert_eq!(evm.db_mut().storage(storage, U256::ZERO), Ok(value));
+    }
+
+    // an account without code returns nothing, so `get` does not decode and the guest
+    // reverts with the empty output.
+    let input = setAndGetCall {
+        target: address!("4000000000000000000000000000000000000004"),
+        value: U256::from(1),
+    }
+    .abi_encode();
+    let result = transact(&mut evm, riscv_caller, input);
+    assert!(matches!(result, ExecutionResult::Revert { output, .. } if output.is_empty()));
+}
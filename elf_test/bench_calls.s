@@ -0,0 +1,62 @@
+# Calls the zero address, where the benchmarks deploy this contract, with empty
+# input as many times as the calldata word says, and returns the number of
+# successful calls as a big-endian word. The nested calls see zero iterations
+# and return right away. Benchmark of calls, the EVM counterpart is in
+# `bins/revm-test/src/bin/riscv.rs`.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # s1 = calldataload(0), the number of calls
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+    mv s1, a0
+    li s2, 0
+
+loop:
+    beqz s1, done
+    # call(all gas, callee, zero, 0, 0, zero, 0)
+    li a0, -1
+    la a1, callee
+    la a2, zero
+    li a3, 0
+    li a4, 0
+    la a5, zero
+    li a6, 0
+    li t0, 3
+    ecall
+    # a0 is one if the call succeeded
+    add s2, s2, a0
+    addi s1, s1, -1
+    j loop
+
+done:
+    # buf = s2 big-endian, the count fits in the low limb
+    la a0, buf
+    addi t1, a0, 31
+    li t4, 8
+word:
+    sb s2, 0(t1)
+    srli s2, s2, 8
+    addi t1, t1, -1
+    addi t4, t4, -1
+    bnez t4, word
+    # return(buf, 32)
+    li a1, 32
+    li t0, 0
+    ecall
+
+callee:
+    .fill 20, 1, 0
+zero:
+    .fill 32, 1, 0
+buf:
+    .fill 32, 1, 0
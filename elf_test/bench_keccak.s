@@ -0,0 +1,59 @@
+# Hashes a 32-byte buffer as many times as the calldata word says, storing each
+# hash back into the buffer big-endian, as MSTORE does, and returns the last hash.
+# Benchmark of a hashing loop, the EVM counterpart is in
+# `bins/revm-test/src/bin/riscv.rs`.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # s1 = calldataload(0), the number of iterations
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+    mv s1, a0
+    la s2, buf
+
+loop:
+    beqz s1, done
+    # buf = keccak256(buf, 32)
+    mv a0, s2
+    li a1, 32
+    li t0, 0x20
+    ecall
+    # the limbs are little-endian in tmp, reverse them into the big-endian hash at buf
+    la t1, tmp
+    sd a0, 0(t1)
+    sd a1, 8(t1)
+    sd a2, 16(t1)
+    sd a3, 24(t1)
+    addi t3, s2, 31
+    li t4, 32
+reverse:
+    lbu t5, 0(t1)
+    sb t5, 0(t3)
+    addi t1, t1, 1
+    addi t3, t3, -1
+    addi t4, t4, -1
+    bnez t4, reverse
+    addi s1, s1, -1
+    j loop
+
+done:
+    # return(buf, 32)
+    mv a0, s2
+    li a1, 32
+    li t0, 0
+    ecall
+
+    .p2align 3
+buf:
+    .fill 32, 1, 0
+tmp:
+    .fill 32, 1, 0
@@ -0,0 +1,71 @@
+# Stores every key from the calldata word down to one under itself, then loads
+# them all back and returns the sum of the values as a big-endian word.
+# Benchmark of storage access, the EVM counterpart is in
+# `bins/revm-test/src/bin/riscv.rs`.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # s1 = calldataload(0), the number of keys
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+    mv s1, a0
+    mv s2, a0
+    li s3, 0
+
+store:
+    beqz s1, load
+    # sstore(s1, s1)
+    mv a0, s1
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    mv a4, s1
+    li a5, 0
+    li a6, 0
+    li a7, 0
+    li t0, 2
+    ecall
+    addi s1, s1, -1
+    j store
+
+load:
+    beqz s2, done
+    # s3 += sload(s2)
+    mv a0, s2
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 1
+    ecall
+    add s3, s3, a0
+    addi s2, s2, -1
+    j load
+
+done:
+    # buf = s3 big-endian, the sum fits in the low limb
+    la a0, buf
+    addi t1, a0, 31
+    li t4, 8
+word:
+    sb s3, 0(t1)
+    srli s3, s3, 8
+    addi t1, t1, -1
+    addi t4, t4, -1
+    bnez t4, word
+    # return(buf, 32)
+    li a1, 32
+    li t0, 0
+    ecall
+
+    .p2align 3
+buf:
+    .fill 32, 1, 0
@@ -0,0 +1,65 @@
+# Calls the address at `callee` with the scheme selected by the first byte of
+# the calldata (3 = CALL, 0xF2 = CALLCODE, 0xF4 = DELEGATECALL,
+# 0xFA = STATICCALL), the value 2^64 + 7 at `value` where the scheme takes
+# one, a gas limit of 50000 and the 4-byte input at `args`. Returns the 32 bytes of output copied to `ret`.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # the scheme is the top byte of calldataload(0)
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+    srli t0, a3, 56
+    li t2, 3
+    beq t0, t2, transfer
+    li t2, 0xF2
+    beq t0, t2, transfer
+
+    # delegatecall/staticcall(50000, callee, args, 4, ret, 32)
+    li a0, 50000
+    la a1, callee
+    la a2, args
+    li a3, 4
+    la a4, ret
+    li a5, 32
+    ecall
+    j done
+
+transfer:
+    # call/callcode(50000, callee, value, args, 4, ret, 32)
+    li a0, 50000
+    la a1, callee
+    la a2, value
+    la a3, args
+    li a4, 4
+    la a5, ret
+    li a6, 32
+    ecall
+
+done:
+    # return(ret, 32)
+    la a0, ret
+    li a1, 32
+    li t0, 0
+    ecall
+
+callee:
+    .fill 19, 1, 0x11
+    .byte 0x22
+value:
+    .fill 23, 1, 0
+    .byte 1
+    .fill 7, 1, 0
+    .byte 7
+args:
+    .ascii "ping"
+ret:
+    .fill 32, 1, 0
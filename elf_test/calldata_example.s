@@ -0,0 +1,62 @@
+# Stores the calldata size under storage key 0 and the calldata word at
+# offset 1 under key 1, then returns 40 bytes of calldata copied from
+# offset 2.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # calldatasize()
+    li t0, 0x36
+    ecall
+
+    # sstore(0, size)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # calldataload(1)
+    li a0, 1
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+
+    # sstore(1, word)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 1
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # calldatacopy(buf, 2, 40)
+    la a0, buf
+    li a1, 2
+    li a2, 40
+    li t0, 0x37
+    ecall
+
+    # return(buf, 40)
+    la a0, buf
+    li a1, 40
+    li t0, 0
+    ecall
+
+buf:
+    .fill 40, 1, 0xAA
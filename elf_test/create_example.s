@@ -0,0 +1,21 @@
+# Constructor that deploys `sstore_and_sload_example` as its runtime code by
+# returning the RISC-V marker byte followed by the runtime ELF.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # return(runtime, runtime_end - runtime)
+    la a0, runtime
+    la a1, runtime_end
+    sub a1, a1, a0
+    li t0, 0
+    ecall
+
+runtime:
+    .byte 0xFF
+    .incbin "sstore_and_sload_example"
+runtime_end:
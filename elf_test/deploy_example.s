@@ -0,0 +1,67 @@
+# Deploys `create_example` with CREATE and with CREATE2 and salt 1, storing
+# the created addresses under storage keys 0 and 1, then returns empty
+# output.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # create(zero, initcode, initcode_end - initcode)
+    la a0, zero
+    la a1, initcode
+    la a2, initcode_end
+    sub a2, a2, a1
+    li t0, 0xF0
+    ecall
+
+    # sstore(0, address)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # create2(zero, initcode, initcode_end - initcode, 1)
+    la a0, zero
+    la a1, initcode
+    la a2, initcode_end
+    sub a2, a2, a1
+    li a3, 1
+    li a4, 0
+    li a5, 0
+    li a6, 0
+    li t0, 0xF5
+    ecall
+
+    # sstore(1, address)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 1
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # return(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 0
+    ecall
+
+zero:
+    .fill 32, 1, 0
+initcode:
+    .byte 0xFF
+    .incbin "create_example"
+initcode_end:
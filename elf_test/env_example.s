@@ -0,0 +1,56 @@
+# Calls environment syscalls with zeroed arguments and stores each result
+# under a storage key equal to the syscall number.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .macro query num
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, \num
+    ecall
+
+    # sstore(num, result)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, \num
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+    .endm
+
+    .text
+    .globl _start
+_start:
+    query 0x30 # address
+    query 0x31 # balance
+    query 0x32 # origin
+    query 0x33 # caller
+    query 0x34 # callvalue
+    query 0x3A # gasprice
+    query 0x3B # extcodesize
+    query 0x3F # extcodehash
+    query 0x40 # blockhash
+    query 0x41 # coinbase
+    query 0x42 # timestamp
+    query 0x43 # number
+    query 0x44 # prevrandao
+    query 0x45 # gaslimit
+    query 0x46 # chainid
+    query 0x47 # selfbalance
+    query 0x48 # basefee
+    query 0x49 # blobhash
+    query 0x4A # blobbasefee
+
+    # return(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 0
+    ecall
@@ -0,0 +1,12 @@
+# Spins forever, only running out of gas stops it.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+loop:
+    addi a0, a0, 1
+    j loop
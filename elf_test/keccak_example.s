@@ -0,0 +1,54 @@
+# Stores the Keccak-256 hash of "hello" under storage key 0 and the hash of
+# empty input under key 1, then returns empty output.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # keccak256(data, 5)
+    la a0, data
+    li a1, 5
+    li t0, 0x20
+    ecall
+
+    # sstore(0, hash)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # keccak256(data, 0)
+    la a0, data
+    li a1, 0
+    li t0, 0x20
+    ecall
+
+    # sstore(1, hash)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 1
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # return(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 0
+    ecall
+
+data:
+    .ascii "hello\0\0\0"
@@ -0,0 +1,32 @@
+# Emits a LOG2 with data "hello" and topics 1 and 2, then returns empty
+# output.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # log2(data, 5, topic1, topic2)
+    la a0, data
+    li a1, 5
+    la a2, topic1
+    la a3, topic2
+    li t0, 0xA2
+    ecall
+
+    # return(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 0
+    ecall
+
+data:
+    .ascii "hello\0\0\0"
+topic1:
+    .fill 31, 1, 0
+    .byte 1
+topic2:
+    .fill 31, 1, 0
+    .byte 2
@@ -0,0 +1,27 @@
+# Stores a doubleword at the guest address in the first calldata word and
+# returns the 8 bytes stored there.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # the address is the low limb of calldataload(0)
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+    mv s0, a0
+
+    li t1, 0x1122334455667788
+    sd t1, 0(s0)
+
+    # return(address, 8)
+    mv a0, s0
+    li a1, 8
+    li t0, 0
+    ecall
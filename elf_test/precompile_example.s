@@ -0,0 +1,52 @@
+# Runs the precompile at the address in the first 20 bytes of the calldata
+# with the rest of the calldata as input and a gas limit of 100000, then
+# returns the first 32 bytes of its output, or reverts with empty output if
+# it failed.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # calldatacopy(input, 0, calldatasize())
+    li t0, 0x36
+    ecall
+    mv s0, a0
+    la s1, input
+    mv a2, s0
+    li a1, 0
+    mv a0, s1
+    li t0, 0x37
+    ecall
+
+    # precompile(input, input + 20, calldatasize() - 20, 100000, out, 32)
+    mv a0, s1
+    addi a1, s1, 20
+    addi a2, s0, -20
+    li a3, 100000
+    la a4, out
+    li a5, 32
+    li t0, 5
+    ecall
+    beqz a0, fail
+
+    # return(out, 32)
+    la a0, out
+    li a1, 32
+    li t0, 0
+    ecall
+
+fail:
+    # revert(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 4
+    ecall
+
+    .p2align 3
+out:
+    .fill 32, 1, 0
+# the calldata is copied past the end of the program.
+input:
@@ -0,0 +1,76 @@
+# Calls the address in the first 20 bytes of the calldata with the rest of the
+# calldata as input and up to 64 bytes of output copied to `out`, then returns
+# those bytes of output, or reverts with them if the call failed. Every call
+# increments the counter in storage slot zero before making its call.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # sstore(0, sload(0) + 1)
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 1
+    ecall
+    addi a4, a0, 1
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # calldatacopy(input, 0, calldatasize())
+    li t0, 0x36
+    ecall
+    mv s0, a0
+    la s1, input
+    mv a2, s0
+    li a1, 0
+    mv a0, s1
+    li t0, 0x37
+    ecall
+
+    # call(all gas, input, zero, input + 20, calldatasize() - 20, out, 64)
+    li a0, -1
+    mv a1, s1
+    la a2, zero
+    addi a3, s1, 20
+    addi a4, s0, -20
+    la a5, out
+    li a6, 64
+    li t0, 3
+    ecall
+    mv s2, a0
+
+    # len = min(returndatasize(), 64)
+    li t0, 0x3D
+    ecall
+    li t1, 64
+    bleu a0, t1, 1f
+    mv a0, t1
+1:
+    # return or revert(out, len)
+    mv a1, a0
+    la a0, out
+    li t0, 0
+    bnez s2, 2f
+    li t0, 4
+2:
+    ecall
+
+zero:
+    .fill 32, 1, 0
+    .p2align 3
+out:
+    .fill 64, 1, 0
+# the calldata is copied past the end of the program.
+input:
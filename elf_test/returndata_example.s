@@ -0,0 +1,76 @@
+# Calls the address at `callee`, stores the call status under storage key 0
+# and the return data size under key 1, then copies the return data to
+# `buf` and returns it if the call succeeded or reverts with it otherwise.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # call(all gas, callee, zero, 0, 0, buf, 0)
+    li a0, -1
+    la a1, callee
+    la a2, zero
+    li a3, 0
+    li a4, 0
+    la a5, buf
+    li a6, 0
+    li t0, 3
+    ecall
+    mv s2, a0
+
+    # sstore(0, status)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # returndatasize()
+    li t0, 0x3D
+    ecall
+    mv s1, a0
+
+    # sstore(1, size)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 1
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # returndatacopy(buf, 0, size)
+    la a0, buf
+    li a1, 0
+    mv a2, s1
+    li t0, 0x3E
+    ecall
+
+    la a0, buf
+    mv a1, s1
+    beqz s2, fail
+    # return(buf, size)
+    li t0, 0
+    ecall
+fail:
+    # revert(buf, size)
+    li t0, 4
+    ecall
+
+callee:
+    .fill 20, 1, 0x11
+zero:
+    .fill 32, 1, 0
+buf:
+    .fill 64, 1, 0
@@ -0,0 +1,25 @@
+# Copies one byte of return data without having made a call, which is past
+# the end of the empty return data.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # returndatacopy(buf, 0, 1)
+    la a0, buf
+    li a1, 0
+    li a2, 1
+    li t0, 0x3E
+    ecall
+
+    # return(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 0
+    ecall
+
+buf:
+    .fill 8, 1, 0
@@ -0,0 +1,19 @@
+# Self-destructs, sending the balance of the contract to the address in the
+# first calldata word.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # selfdestruct(calldataload(0))
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+    li t0, 0xFF
+    ecall
@@ -0,0 +1,50 @@
+# Stores a word under a key wider than 64 bits, loads it back and returns
+# empty output if every limb round-trips, reverts otherwise.
+#
+# Words are passed as four little-endian u64 limbs in consecutive registers.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # sstore(key = 2^192 + 42, value)
+    li a0, 42
+    li a1, 0
+    li a2, 0
+    li a3, 1
+    li a4, 0xdeadbeef
+    li a5, 0x11
+    li a6, 0x22
+    li a7, 0x33
+    li t0, 2
+    ecall
+
+    # value = sload(key)
+    li a0, 42
+    li a1, 0
+    li a2, 0
+    li a3, 1
+    li t0, 1
+    ecall
+
+    li t1, 0xdeadbeef
+    bne a0, t1, fail
+    li t1, 0x11
+    bne a1, t1, fail
+    li t1, 0x22
+    bne a2, t1, fail
+    li t1, 0x33
+    bne a3, t1, fail
+
+    # return(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 0
+    ecall
+
+fail:
+    li t0, 4
+    ecall
@@ -0,0 +1,102 @@
+# Solidity ABI contract with `set(uint256)` and `get() returns (uint256)`, keeping
+# the value in storage slot zero. Other selectors and short calldata revert with
+# empty output, like a Solidity contract without a fallback function.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # s1 = calldatasize()
+    li t0, 0x36
+    ecall
+    mv s1, a0
+    li t1, 4
+    bltu s1, t1, fail
+
+    # the selector is the high half of the most significant limb of calldataload(0)
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+    srli s0, a3, 32
+
+    # set(uint256)
+    li t1, 0x60fe47b1
+    beq s0, t1, set
+    # get()
+    li t1, 0x6d4ce63c
+    beq s0, t1, get
+
+fail:
+    # revert(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 4
+    ecall
+
+set:
+    li t1, 36
+    bltu s1, t1, fail
+    # sstore(0, calldataload(4))
+    li a0, 4
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x35
+    ecall
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+    # return(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 0
+    ecall
+
+get:
+    # sload(0)
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 1
+    ecall
+    # the limbs are little-endian in buf, reverse them into the big-endian word at out
+    la t1, buf
+    sd a0, 0(t1)
+    sd a1, 8(t1)
+    sd a2, 16(t1)
+    sd a3, 24(t1)
+    la t2, out
+    addi t3, t2, 31
+    li t4, 32
+reverse:
+    lbu t5, 0(t1)
+    sb t5, 0(t3)
+    addi t1, t1, 1
+    addi t3, t3, -1
+    addi t4, t4, -1
+    bnez t4, reverse
+    # return(out, 32)
+    mv a0, t2
+    li a1, 32
+    li t0, 0
+    ecall
+
+    .p2align 3
+buf:
+    .fill 32, 1, 0
+out:
+    .fill 32, 1, 0
@@ -0,0 +1,47 @@
+# Stores 7 under key 1 in transient storage, loads it back and stores it in
+# storage slot 0, then returns empty output.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # tstore(1, 7)
+    li a0, 1
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li a4, 7
+    li a5, 0
+    li a6, 0
+    li a7, 0
+    li t0, 0x5D
+    ecall
+
+    # tload(1), the value is left in a0..a3
+    li a0, 1
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 0x5C
+    ecall
+
+    # sstore(0, value)
+    mv a4, a0
+    mv a5, a1
+    mv a6, a2
+    mv a7, a3
+    li a0, 0
+    li a1, 0
+    li a2, 0
+    li a3, 0
+    li t0, 2
+    ecall
+
+    # return(0, 0)
+    li a0, 0
+    li a1, 0
+    li t0, 0
+    ecall
@@ -0,0 +1,20 @@
+# Makes the unknown syscall 0x99 if the calldata is empty, and executes an
+# illegal instruction otherwise.
+#
+# Built with:
+#   llvm-mc -triple=riscv64 -mattr=+m,+a,+c,-relax -filetype=obj
+# and linked as a single PT_LOAD segment at 0x80000000.
+
+    .text
+    .globl _start
+_start:
+    # calldatasize()
+    li t0, 0x36
+    ecall
+    bnez a0, illegal
+
+    li t0, 0x99
+    ecall
+
+illegal:
+    unimp